@@ -0,0 +1,26 @@
+use mazes::{
+    generators::{RecursiveBacktracker, SimplifiedPrims, Wilsons},
+    kind, RenderOptions,
+};
+
+fn main() {
+    let options = RenderOptions::default();
+
+    let kind = kind::Regular::new(12, 12);
+    mazes::Grid::new(kind, None, None)
+        .replay(&RecursiveBacktracker, &mut rand::thread_rng())
+        .save_gif("images/recursive_backtracker.gif", 20, 0.0, &options)
+        .expect("gif to be saved");
+
+    let kind = kind::Regular::new(12, 12);
+    mazes::Grid::new(kind, None, None)
+        .replay(&SimplifiedPrims, &mut rand::thread_rng())
+        .save_gif("images/prims_simplified.gif", 20, 0.0, &options)
+        .expect("gif to be saved");
+
+    let kind = kind::Hex::new(10, 10);
+    mazes::Grid::new(kind, None, None)
+        .replay(&Wilsons, &mut rand::thread_rng())
+        .save_gif("images/wilsons_hex.gif", 20, 0.0, &options)
+        .expect("gif to be saved");
+}
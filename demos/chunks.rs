@@ -0,0 +1,29 @@
+use mazes::{generators, Chunks};
+
+fn main() {
+    const SEED: u64 = 2024;
+
+    let chunks = Chunks::new(SEED, 8, 8);
+
+    // neighbours agree on their shared borders without either knowing about the other
+    for (row, col) in [(0, 0), (-3, 7), (1_000_000, -42)] {
+        let chunk = chunks.chunk(row, col);
+        assert_eq!(chunk.east, chunks.chunk(row, col + 1).west);
+        assert_eq!(chunk.south, chunks.chunk(row + 1, col).north);
+        assert!(chunk.grid.is_perfect());
+    }
+
+    // and a chunk comes out the same each time it's paged back in
+    let chunk = chunks.chunk(5, -5);
+    assert_eq!(chunk.grid.to_string(), chunks.chunk(5, -5).grid.to_string());
+
+    let grid = chunks.stitch(-1..2, -1..2, &generators::Kruskals);
+    assert!(grid.is_connected());
+    println!("{grid}");
+    grid.save_png("chunks", 15, 0.0);
+
+    let chunks = Chunks::new(SEED, 10, 16).openings(3);
+    let grid = chunks.stitch(4..7, 9..11, &generators::RecursiveDivision);
+    assert!(grid.is_connected());
+    grid.save_png("chunks_recursive_division", 15, 0.0);
+}
@@ -0,0 +1,56 @@
+use mazes::{generators::Registry, kind, kind::Kind, Grid};
+
+fn check<K: Kind>(name: &str, kind: impl Fn() -> K) {
+    for generator in Registry::<K>::new().iter() {
+        let grid = Grid::new(kind(), None, None).generate(generator, &mut rand::thread_rng());
+
+        assert!(
+            grid.is_connected(),
+            "{name} {} is connected",
+            generator.name()
+        );
+        println!(
+            "{name:<9} {:<22} perfect: {:<5} loops: {:<3} invalid links: {}",
+            generator.name(),
+            grid.is_perfect(),
+            grid.cycles().len(),
+            grid.invalid_links().len()
+        );
+    }
+}
+
+fn main() {
+    check("regular", || kind::Regular::new(12, 12));
+    check("polar", || kind::Polar::new(8));
+    check("hex", || kind::Hex::new(12, 12));
+    check("triangle", || kind::Triangle::new(12, 12));
+    check("upsilon", || kind::Upsilon::new(12, 12));
+    check("weave", || kind::Weave::new(12, 12));
+    check("3d", || kind::ThreeD::new(6, 6, 3));
+    check("cylinder", || kind::Cylinder::new(12, 12));
+    check("torus", || kind::Torus::new(12, 12));
+    check("mobius", || kind::MobiusStrip::new(12, 12));
+    check("klein", || kind::KleinBottle::new(12, 12));
+    check("cube", || kind::Cube::new(6));
+
+    let kind = kind::Weave::new(12, 12);
+    let grid = Grid::new(kind, None, None).kruskals_better_weave();
+    assert!(grid.is_perfect() && grid.invalid_links().is_empty());
+
+    let kind = kind::Regular::new(12, 12);
+    let grid = Grid::new(kind, None, None).recursive_backtracker();
+    assert!(grid.is_perfect());
+
+    let grid = grid.braid(1.0);
+    let cycles = grid.cycles();
+    assert!(!grid.is_perfect() && !cycles.is_empty());
+    for cycle in &cycles {
+        let closed = cycle.iter().zip(cycle.iter().cycle().skip(1));
+        assert!(closed.into_iter().all(|(a, b)| grid.are_linked(*a, *b)));
+    }
+    println!("braided: {} loops, the first {:?}", cycles.len(), cycles[0]);
+
+    let kind = kind::Regular::new(4, 4);
+    let grid = Grid::new(kind, None, None);
+    assert_eq!(grid.components().len(), 16);
+}
@@ -0,0 +1,50 @@
+use image::Rgb;
+use mazes::{kind, solvers, Grid, RenderOptions};
+use rand::seq::SliceRandom;
+
+fn main() {
+    // every cell has four distinct neighbours, and each of them has it back
+    let grid = Grid::new(kind::Cube::new(4), None, None);
+    for cell in grid.cells() {
+        let mut around = grid.neighbours(cell).collect::<Vec<_>>();
+        around.sort_unstable();
+        around.dedup();
+        assert_eq!(around.len(), 4);
+        assert!(around
+            .iter()
+            .all(|other| grid.neighbours(*other).any(|back| back == cell)));
+    }
+    assert_eq!(grid.neighbouring_cells().len(), 6 * 4 * 4 * 2);
+
+    let mazes = [
+        Grid::new(kind::Cube::new(8), None, None).recursive_backtracker(),
+        Grid::new(kind::Cube::new(8), None, None).wilsons(),
+        Grid::new(kind::Cube::new(8), None, None).kruskals(),
+        Grid::new(kind::Cube::new(8), None, None)
+            .growing_tree(|list| list.choose(&mut rand::thread_rng())),
+    ];
+    for grid in &mazes {
+        assert!(grid.is_perfect() && grid.invalid_links().is_empty());
+    }
+
+    let mut grid = Grid::new(kind::Cube::new(8), None, None).recursive_backtracker();
+    grid.save_png("cube", 20);
+
+    grid.set_start(grid.get(0, 0, 0).unwrap());
+    grid.save_png("cube_colorized", 20);
+
+    // the far corner of the bottom face, which A* has to reach over the surface
+    grid.set_goal(grid.get(5, 7, 7).unwrap());
+    let steps = grid.distances().expect("goal to be set").cells().count();
+    let solution = grid.solve(&solvers::AStar).expect("goal to be reachable");
+    assert_eq!(solution.path.len(), steps);
+
+    let options = RenderOptions {
+        shade: false,
+        solution: Some(Rgb([220, 40, 40])),
+        markers: true,
+        ..RenderOptions::default()
+    };
+    grid.save_png_to("images/cube_solution.png", 20, 0.0, &options)
+        .expect("image to be saved");
+}
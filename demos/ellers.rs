@@ -10,4 +10,30 @@ fn main() {
     grid.set_start(grid.get_random_cell());
     println!("{grid}");
     grid.save_png("ellers_colorized", 25, 0.0);
+
+    // a ring with a notch cut into it, so rows split into separate runs
+    let mut mask = mazes::Mask::new(12, 12);
+    for row in 3..9 {
+        for col in 3..9 {
+            mask[row][col] = false;
+        }
+    }
+    for col in 0..3 {
+        mask[6][col] = false;
+    }
+    let kind = kind::Masked::new(mask);
+    let grid = mazes::Grid::new(kind, None, None).ellers();
+    assert!(grid.is_perfect());
+    println!("{grid}");
+    grid.save_png("ellers_masked", 25, 0.0);
+
+    let kind = kind::Hex::new(15, 15);
+    let grid = mazes::Grid::new(kind, None, None).ellers();
+    assert!(grid.is_perfect());
+    grid.save_png("ellers_hex", 25);
+
+    let kind = kind::ThreeD::new(6, 6, 3);
+    let grid = mazes::Grid::new(kind, None, None).ellers();
+    assert!(grid.is_perfect());
+    grid.save_png("ellers_3d", 25, 0.0);
 }
@@ -0,0 +1,70 @@
+use std::{env, fs};
+
+use mazes::{
+    generators::{AldousBroder, RecursiveBacktracker},
+    kind, Grid, Mask, MazeError, RenderOptions,
+};
+
+fn main() {
+    let dir = env::temp_dir();
+
+    let missing = Mask::try_from_txt(dir.join("mazes_missing_mask.txt"));
+    assert!(matches!(missing, Err(MazeError::Io(_))));
+    assert!(matches!(
+        Mask::try_from_image(dir.join("mazes_missing_mask.png")),
+        Err(MazeError::Image(_))
+    ));
+
+    let path = dir.join("mazes_uneven_mask.txt");
+    fs::write(&path, "...\n.X\n...\n").expect("mask to be written");
+    let uneven = Mask::try_from_txt(&path);
+    println!(
+        "{}",
+        uneven.as_ref().expect_err("uneven mask to be rejected")
+    );
+    assert!(matches!(uneven, Err(MazeError::UnevenMask { row: 1, .. })));
+
+    let mut mask = Mask::new(2, 2);
+    for row in 0..2 {
+        mask[row] = vec![false; 2];
+    }
+    let grid = Grid::new(kind::Masked::new(mask), None, None);
+    assert!(matches!(
+        grid.try_get_random_cell(),
+        Err(MazeError::EmptyGrid)
+    ));
+    assert!(matches!(
+        grid.try_generate(&RecursiveBacktracker, &mut rand::thread_rng()),
+        Err(MazeError::EmptyGrid)
+    ));
+
+    let mut mask = Mask::new(3, 3);
+    mask[0][1] = false;
+    mask[1][0] = false;
+    let grid = Grid::new(kind::Masked::new(mask), None, None);
+    let cut_off = grid.try_generate(&AldousBroder, &mut rand::thread_rng());
+    println!("{}", cut_off.as_ref().err().expect("corner to be cut off"));
+    assert!(matches!(cut_off, Err(MazeError::DisconnectedGrid)));
+
+    let kind = kind::Weighted::new(3, 3);
+    let mut grid = Grid::new(kind, None, None);
+    assert!(grid.try_set_weight(1, 1, 5).is_ok());
+    let outside = grid.try_set_weight(3, 0, 5);
+    println!("{}", outside.as_ref().expect_err("cell to be missing"));
+
+    // passages with nowhere else to go, and weaves too small to cross
+    let kind = kind::Regular::new(1, 5);
+    let grid = Grid::new(kind, None, None)
+        .recursive_backtracker()
+        .braid(1.0);
+    assert!(grid.is_perfect());
+
+    let kind = kind::Weave::new(2, 3);
+    let grid = Grid::new(kind, None, None).kruskals_better_weave();
+    assert!(grid.is_perfect());
+
+    let mut png = Vec::new();
+    grid.write_png(&mut png, 10, 0.0, &RenderOptions::default())
+        .expect("flat weave to fall back to an inset");
+    grid.to_image(10, 0.0, &RenderOptions::default());
+}
@@ -1,16 +1,23 @@
-use mazes::{cell, kind};
+use mazes::kind;
 
 fn main() {
     let kind = kind::Regular::new(16, 16);
-    let mut grid = mazes::Grid::new(kind, None, None).binary_tree();
-
-    let (new_start, _) = grid
-        .distances_from(cell::RegularCell { row: 0, col: 0 })
-        .max();
-    grid.set_start(new_start);
-
-    let (goal, _) = grid.distances_from(new_start).max();
-    grid.set_goal(goal);
+    let grid = mazes::Grid::new(kind, None, None)
+        .binary_tree()
+        .with_longest_path();
 
     println!("{grid}");
+
+    let kind = kind::Regular::new(12, 12);
+    let grid = mazes::Grid::new(kind, None, None)
+        .recursive_backtracker()
+        .braid(1.0);
+    let sweep = grid.longest_path().expect("grid to have cells");
+    let exact = grid.longest_path_exact().expect("grid to have cells");
+    assert!(sweep.cost() <= exact.cost());
+    println!(
+        "braided diameter: {} by sweeping, {} exactly",
+        sweep.cost(),
+        exact.cost()
+    );
 }
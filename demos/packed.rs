@@ -0,0 +1,17 @@
+use mazes::kind;
+
+fn main() {
+    const SIZE: usize = 300;
+
+    let kind = kind::Regular::new(SIZE, SIZE);
+    let grid = mazes::Grid::new_packed(kind, None, None).recursive_backtracker();
+    println!(
+        "{SIZE}x{SIZE} packed maze with {} dead-ends",
+        grid.dead_ends().len()
+    );
+
+    let kind = kind::ThreeD::new(5, 5, 3);
+    let mut grid = mazes::Grid::new_packed(kind, None, None).kruskals();
+    grid.set_start(grid.get_random_cell());
+    grid.save_png("3d_packed", 50, 0.0);
+}
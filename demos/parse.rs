@@ -0,0 +1,59 @@
+use mazes::{kind, Grid, Mask};
+
+const ASCII: &str = "\
++---+---+---+---+
+|               |
++---+---+---+   +
+|           |   |
++   +---+   +   +
+|   |       |   |
++   +---+---+   +
+|               |
++---+---+---+---+
+";
+
+fn main() {
+    let kind = kind::Regular::new(8, 12);
+    let grid = Grid::new(kind, None, None).recursive_backtracker();
+    let text = grid.to_string();
+    let parsed = text.parse::<Grid<kind::Regular>>().expect("maze to parse");
+    assert_eq!(parsed.to_string(), text);
+
+    // distances written in the cells don't get in the way
+    let grid = grid.with_longest_path();
+    let parsed = grid
+        .to_string()
+        .parse::<Grid<kind::Regular>>()
+        .expect("maze with distances to parse");
+    assert!(grid.cells().into_iter().all(|cell| grid
+        .neighbours(cell)
+        .all(|other| grid.are_linked(cell, other) == parsed.are_linked(cell, other))));
+
+    let mut mask = Mask::new(6, 8);
+    for row in 2..4 {
+        for col in 3..5 {
+            mask[row][col] = false;
+        }
+    }
+    mask[0][0] = false;
+    let kind = kind::Masked::new(mask);
+    let grid = Grid::new(kind, None, None).recursive_backtracker();
+    let text = grid.to_string();
+    let parsed = text
+        .parse::<Grid<kind::Masked>>()
+        .expect("masked maze to parse");
+    assert_eq!(parsed.size(), grid.size());
+    assert_eq!(parsed.to_string(), text);
+
+    let mut grid = ASCII
+        .parse::<Grid<kind::Regular>>()
+        .expect("ascii maze to parse");
+    assert!(grid.is_perfect());
+    grid.set_start(grid.get(0, 0).expect("top left cell"));
+    grid.set_goal(grid.get(3, 3).expect("bottom right cell"));
+    println!("{grid}");
+    grid.save_png("parsed", 25, 0.0);
+
+    let invalid = "+---+\n|   |\n".parse::<Grid<kind::Regular>>();
+    println!("{}", invalid.err().expect("unclosed maze to be rejected"));
+}
@@ -0,0 +1,62 @@
+use mazes::{
+    kind,
+    solvers::{AStar, Tremaux},
+    Direction, Distances, Path,
+};
+
+fn main() {
+    let kind = kind::Weighted::new(10, 10);
+    let mut grid = mazes::Grid::new(kind, None, None)
+        .recursive_backtracker()
+        .braid(0.5);
+    grid.set_start(grid.get(0, 0).unwrap());
+    grid.set_goal(grid.get(9, 9).unwrap());
+    let middle = grid.get(5, 5).unwrap();
+    grid.set_weight(middle.row, middle.col, 50);
+
+    let path = grid.path().expect("start and goal to be set");
+    let solution = grid.solve(&AStar).expect("goal to be reachable");
+    let solved = Path::new(solution.path).expect("solution to have cells");
+    assert_eq!(path.cost(), solved.cost());
+
+    let distances = Distances::from(solved.clone());
+    assert_eq!(distances[solved.goal()], solved.cost());
+    assert_eq!(Path::from(distances), solved);
+
+    let directions = solved
+        .steps(&grid)
+        .map(
+            |step| match step.direction.expect("steps to be between neighbours") {
+                Direction::North => 'N',
+                Direction::South => 'S',
+                Direction::East => 'E',
+                Direction::West => 'W',
+                direction => unreachable!("{direction:?} on a rectangular grid"),
+            },
+        )
+        .collect::<String>();
+    println!(
+        "{} cells costing {}: {directions}",
+        solved.len(),
+        solved.cost()
+    );
+
+    let walked =
+        Path::<kind::Weighted>::new(grid.solve(&Tremaux).expect("goal to be reachable").path)
+            .expect("walk to have cells");
+    println!("walking it by hand took {} steps", walked.len() - 1);
+
+    let kind = kind::Polar::new(8);
+    let mut grid = mazes::Grid::new(kind, None, None).recursive_backtracker();
+    grid.set_start(grid.get(0, 0).unwrap());
+    grid.set_goal(grid.get(7, 0).unwrap());
+    let path = grid.path().expect("start and goal to be set");
+    assert!(path.steps(&grid).all(|step| step.direction.is_some()));
+
+    let kind = kind::Hex::new(8, 8);
+    let mut grid = mazes::Grid::new(kind, None, None).recursive_backtracker();
+    grid.set_start(grid.get(0, 0).unwrap());
+    grid.set_goal(grid.get(7, 7).unwrap());
+    let path = grid.path().expect("start and goal to be set");
+    assert!(path.steps(&grid).all(|step| step.direction.is_some()));
+}
@@ -0,0 +1,93 @@
+use mazes::{kind, Grid, Mask, Persist};
+
+fn round_trip<K: Persist>(name: &str, grid: &Grid<K>) -> Grid<K> {
+    let json = grid.to_json();
+    let loaded = Grid::<K>::from_json(&json).expect("saved grid to load");
+
+    assert_eq!(loaded.to_json(), json, "{name} reloads as saved");
+    let mut cells = grid.cells();
+    let mut loaded_cells = loaded.cells();
+    cells.sort_unstable();
+    loaded_cells.sort_unstable();
+    assert_eq!(cells, loaded_cells, "{name} reloads every cell");
+    assert!(cells.iter().all(|cell| grid
+        .links(*cell)
+        .all(|other| loaded.are_linked(*cell, other))));
+    assert_eq!((grid.start(), grid.goal()), (loaded.start(), loaded.goal()));
+
+    println!("{name}: {} bytes", json.len());
+    loaded
+}
+
+fn main() {
+    let kind = kind::Regular::new(4, 4);
+    let grid = Grid::new(kind, None, None)
+        .recursive_backtracker()
+        .with_longest_path();
+    let loaded = round_trip("regular", &grid);
+    assert_eq!(grid.to_string(), loaded.to_string());
+    println!("{}", grid.to_json());
+
+    let mut mask = Mask::new(6, 6);
+    mask[2][2] = false;
+    mask[2][3] = false;
+    let kind = kind::Masked::new(mask);
+    round_trip("masked", &Grid::new(kind, None, None).wilsons());
+
+    let kind = kind::Polar::new(6);
+    round_trip("polar", &Grid::new(kind, None, None).aldous_broder());
+
+    let kind = kind::Hex::new(6, 6);
+    round_trip("hex", &Grid::new(kind, None, None).hunt_and_kill());
+
+    let kind = kind::Triangle::new(6, 8);
+    round_trip("triangle", &Grid::new(kind, None, None).kruskals());
+
+    let kind = kind::Upsilon::new(6, 6);
+    round_trip(
+        "upsilon",
+        &Grid::new(kind, None, None).recursive_backtracker(),
+    );
+
+    let kind = kind::Weighted::new(6, 6);
+    let mut grid = Grid::new(kind, None, None)
+        .recursive_backtracker()
+        .braid(0.5);
+    grid.set_weight(2, 3, 50);
+    round_trip("weighted", &grid.with_longest_path());
+
+    let kind = kind::Weave::new(12, 12);
+    let mut grid = Grid::new(kind, None, None).kruskals_better_weave();
+    let under = grid
+        .cells()
+        .into_iter()
+        .find(|cell| !cell.is_over())
+        .expect("a crossing");
+    grid.set_start(under);
+    round_trip("weave", &grid);
+
+    let kind = kind::ThreeD::new(3, 3, 3);
+    round_trip("3d", &Grid::new(kind, None, None).recursive_backtracker());
+
+    let kind = kind::Cube::new(3);
+    round_trip("cube", &Grid::new(kind, None, None).wilsons());
+
+    // loading checks what it's given
+    let json = Grid::new(kind::Regular::new(2, 2), None, None).to_json();
+    let wrong_kind = Grid::<kind::Hex>::from_json(&json);
+    println!(
+        "{}",
+        wrong_kind
+            .as_ref()
+            .err()
+            .expect("hex to reject a regular grid")
+    );
+    let jump = json.replace("\"links\":[]", "\"links\":[[0,0,1,1]]");
+    let jump = Grid::<kind::Regular>::from_json(&jump);
+    println!(
+        "{}",
+        jump.as_ref()
+            .err()
+            .expect("diagonal passage to be rejected")
+    );
+}
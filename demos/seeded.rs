@@ -0,0 +1,16 @@
+use mazes::kind;
+use rand::{rngs::StdRng, SeedableRng};
+
+fn main() {
+    const SEED: u64 = 42;
+
+    let kind = kind::Regular::new(16, 16);
+    let grid =
+        mazes::Grid::new(kind, None, None).wilsons_with_rng(&mut StdRng::seed_from_u64(SEED));
+    println!("{grid}");
+
+    let kind = kind::Regular::new(16, 16);
+    let again =
+        mazes::Grid::new(kind, None, None).wilsons_with_rng(&mut StdRng::seed_from_u64(SEED));
+    assert_eq!(grid.to_string(), again.to_string());
+}
@@ -0,0 +1,54 @@
+use image::Rgb;
+use mazes::{kind, RenderOptions};
+
+fn main() {
+    let options = RenderOptions {
+        shade: false,
+        solution: Some(Rgb([220, 40, 40])),
+        solution_thickness: 3,
+        markers: true,
+        ..RenderOptions::default()
+    };
+
+    let kind = kind::Regular::new(15, 15);
+    let mut grid = mazes::Grid::new(kind, None, None).recursive_backtracker();
+    grid.set_start(grid.get(0, 0).unwrap());
+    grid.set_goal(grid.get(14, 14).unwrap());
+    grid.save_png_to("images/solution.png", 20, 0.0, &options)
+        .expect("image to be saved");
+
+    let kind = kind::Polar::new(10);
+    let mut grid = mazes::Grid::new(kind, None, None).recursive_backtracker();
+    grid.set_start(grid.get(0, 0).unwrap());
+    grid.set_goal(grid.get(9, 0).unwrap());
+    grid.save_png_to("images/solution_circle.png", 20, 0.0, &options)
+        .expect("image to be saved");
+
+    let kind = kind::Hex::new(10, 10);
+    let mut grid = mazes::Grid::new(kind, None, None).recursive_backtracker();
+    grid.set_start(grid.get(0, 0).unwrap());
+    grid.set_goal(grid.get(9, 9).unwrap());
+    grid.save_png_to("images/solution_hex.png", 20, 0.0, &options)
+        .expect("image to be saved");
+
+    let kind = kind::Triangle::new(10, 17);
+    let mut grid = mazes::Grid::new(kind, None, None).recursive_backtracker();
+    grid.set_start(grid.get(0, 0).unwrap());
+    grid.set_goal(grid.get(9, 16).unwrap());
+    grid.save_png_to("images/solution_triangle.png", 25, 0.0, &options)
+        .expect("image to be saved");
+
+    let kind = kind::Weave::new(12, 12);
+    let mut grid = mazes::Grid::new(kind, None, None).kruskals_better_weave();
+    grid.set_start(grid.get(0, 0).unwrap());
+    grid.set_goal(grid.get(11, 11).unwrap());
+    grid.save_png_to("images/solution_weave.png", 25, 0.1, &options)
+        .expect("image to be saved");
+
+    let kind = kind::ThreeD::new(5, 5, 3);
+    let mut grid = mazes::Grid::new(kind, None, None).recursive_backtracker();
+    grid.set_start(grid.get(0, 0, 0).unwrap());
+    grid.set_goal(grid.get(4, 4, 2).unwrap());
+    grid.save_png_to("images/solution_3d.png", 25, 0.0, &options)
+        .expect("image to be saved");
+}
@@ -0,0 +1,84 @@
+use mazes::{
+    kind,
+    solvers::{self, Hand, Solver, WallFollower},
+    Grid,
+};
+
+fn main() {
+    let kind = kind::Regular::new(40, 40);
+    let mut grid = Grid::new(kind, None, None)
+        .recursive_backtracker()
+        .braid(0.5);
+    grid.set_start(grid.get(0, 0).unwrap());
+    grid.set_goal(grid.get(39, 39).unwrap());
+    report("regular", &grid, solvers::all());
+
+    let kind = kind::Regular::new(40, 40);
+    let mut grid = Grid::new(kind, None, None).wilsons();
+    grid.set_start(grid.get(0, 0).unwrap());
+    grid.set_goal(grid.get(39, 39).unwrap());
+    report("perfect regular", &grid, with_wall_followers());
+
+    let kind = kind::Hex::new(30, 30);
+    let mut grid = Grid::new(kind, None, None).recursive_backtracker();
+    grid.set_start(grid.get(0, 0).unwrap());
+    grid.set_goal(grid.get(29, 29).unwrap());
+    report("hex", &grid, with_wall_followers());
+
+    let kind = kind::Triangle::new(20, 30);
+    let mut grid = Grid::new(kind, None, None).recursive_backtracker();
+    grid.set_start(grid.get(0, 0).unwrap());
+    grid.set_goal(grid.get(19, 29).unwrap());
+    report("triangle", &grid, with_wall_followers());
+
+    let kind = kind::Polar::new(20);
+    let mut grid = Grid::new(kind, None, None).recursive_backtracker();
+    grid.set_start(grid.get(0, 0).unwrap());
+    grid.set_goal(grid.get(19, 0).unwrap());
+    report("polar", &grid, with_wall_followers());
+
+    let kind = kind::Weave::new(20, 20);
+    let mut grid = Grid::new(kind, None, None).kruskals_better_weave();
+    grid.set_start(grid.get(0, 0).unwrap());
+    grid.set_goal(grid.get(19, 19).unwrap());
+    report("weave", &grid, solvers::all());
+
+    let kind = kind::ThreeD::new(8, 8, 4);
+    let mut grid = Grid::new(kind, None, None).recursive_backtracker();
+    grid.set_start(grid.get(0, 0, 0).unwrap());
+    grid.set_goal(grid.get(7, 7, 3).unwrap());
+    report("3d", &grid, solvers::all());
+}
+
+fn with_wall_followers<K: kind::Heading>() -> Vec<Box<dyn Solver<K>>> {
+    let mut solvers = solvers::all();
+    solvers.push(Box::new(WallFollower(Hand::Left)));
+    solvers.push(Box::new(WallFollower(Hand::Right)));
+    solvers
+}
+
+fn report<K: kind::Kind>(name: &str, grid: &Grid<K>, solvers: Vec<Box<dyn Solver<K>>>) {
+    let steps = grid
+        .distances()
+        .expect("start and goal to be set")
+        .cells()
+        .count();
+
+    for solver in solvers {
+        let solution = grid.solve(solver.as_ref()).expect("goal to be reachable");
+        assert_eq!(solution.path.first().copied(), grid.start());
+        assert_eq!(solution.path.last().copied(), grid.goal());
+        assert!(solution.path.len() >= steps, "{} on {name}", solver.name());
+        assert!(solution
+            .path
+            .windows(2)
+            .all(|pair| grid.are_linked(pair[0], pair[1])));
+
+        println!(
+            "{name}: {} found a {}-cell path expanding {} cells",
+            solver.name(),
+            solution.path.len(),
+            solution.expanded
+        );
+    }
+}
@@ -0,0 +1,22 @@
+use mazes::{generators::Registry, kind};
+
+fn main() {
+    let registry = Registry::<kind::Regular>::new();
+
+    for generator in registry.iter() {
+        let kind = kind::Regular::new(20, 20);
+        let grid = mazes::Grid::new(kind, None, None)
+            .generate(generator, &mut rand::thread_rng())
+            .with_longest_path();
+
+        let stats = grid.stats();
+        println!("{}\n{stats}\n", generator.name());
+    }
+
+    let kind = kind::Hex::new(20, 20);
+    let grid = mazes::Grid::new(kind, None, None)
+        .recursive_backtracker()
+        .braid(0.5)
+        .with_longest_path();
+    println!("braided hex\n{}", grid.stats());
+}
@@ -0,0 +1,23 @@
+use mazes::{generators::RecursiveBacktracker, kind};
+use rand::{rngs::StdRng, SeedableRng};
+
+fn main() {
+    const SEED: u64 = 7;
+
+    let kind = kind::Regular::new(8, 8);
+    let mut replay = mazes::Grid::new(kind, None, None)
+        .replay(&RecursiveBacktracker, &mut StdRng::seed_from_u64(SEED));
+
+    for event in replay.by_ref().take(40) {
+        println!("{event:?}");
+    }
+    println!("{}", replay.grid());
+
+    let grid = replay.finish();
+    println!("{grid}");
+
+    let kind = kind::Regular::new(8, 8);
+    let batch = mazes::Grid::new(kind, None, None)
+        .recursive_backtracker_with_rng(&mut StdRng::seed_from_u64(SEED));
+    assert_eq!(grid.to_string(), batch.to_string());
+}
@@ -0,0 +1,42 @@
+use mazes::generators::EllersRows;
+use rand::{rngs::StdRng, SeedableRng};
+
+fn main() {
+    const SEED: u64 = 7;
+    const COLS: usize = 12;
+
+    // print corridors as they're generated, the way an endless game would ask for them
+    let mut text = format!("+{}\n", "---+".repeat(COLS));
+    let mut rows = EllersRows::new(COLS, StdRng::seed_from_u64(SEED));
+    for row in rows.by_ref().take(9) {
+        text.push_str(&row.to_string());
+    }
+    text.push_str(&rows.last_row().to_string());
+    print!("{text}");
+
+    let grid = text
+        .parse::<mazes::Grid<mazes::kind::Regular>>()
+        .expect("streamed rows to read as a maze");
+    assert!(grid.is_perfect());
+
+    // the same seed streams the same rows
+    let rows = EllersRows::new(COLS, StdRng::seed_from_u64(SEED)).take(200);
+    let again = EllersRows::new(COLS, StdRng::seed_from_u64(SEED)).take(200);
+    assert!(rows.eq(again));
+
+    // closing off the stream at any point gives a perfect maze
+    for height in [1, 2, 50, 500] {
+        let mut rows = EllersRows::new(COLS, StdRng::seed_from_u64(SEED + height as u64));
+        let mut taken = rows.by_ref().take(height - 1).collect::<Vec<_>>();
+        taken.push(rows.last_row());
+        let grid = mazes::Grid::from_rows(taken);
+        assert!(grid.is_connected() && grid.is_perfect());
+    }
+
+    // a million rows take no more memory than one
+    let open = EllersRows::new(COLS, StdRng::seed_from_u64(SEED))
+        .take(1_000_000)
+        .filter(|row| (0..COLS).all(|col| row.is_open_south(col)))
+        .count();
+    println!("{open} of a million rows were open all the way across");
+}
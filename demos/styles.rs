@@ -0,0 +1,40 @@
+use image::Rgb;
+use mazes::{kind, Gradient, RenderOptions};
+
+fn main() {
+    let kind = kind::Regular::new(20, 20);
+    let mut grid = mazes::Grid::new(kind, None, None).recursive_backtracker();
+    grid.set_start(grid.get(10, 10).unwrap());
+
+    let options = RenderOptions {
+        wall: Rgb([40, 40, 60]),
+        wall_thickness: 4,
+        background: Rgb([250, 245, 230]),
+        margin: 12,
+        gradient: Gradient::evenly([
+            Rgb([255, 255, 180]),
+            Rgb([250, 120, 60]),
+            Rgb([120, 20, 90]),
+        ])
+        .expect("colours to be given"),
+        ..RenderOptions::default()
+    };
+    grid.save_png_to("images/styled.png", 20, 0.0, &options)
+        .expect("image to be saved");
+    grid.save_svg_to("images/styled.svg", 20, 0.0, &options)
+        .expect("svg to be saved");
+
+    let kind = kind::Polar::new(10);
+    let grid = mazes::Grid::new(kind, None, None).recursive_backtracker();
+    let options = RenderOptions {
+        wall_thickness: 3,
+        margin: 8,
+        shade: false,
+        ..RenderOptions::default()
+    };
+    grid.save_png_to("images/styled_circle.png", 20, 0.0, &options)
+        .expect("image to be saved");
+
+    assert!(Gradient::evenly([]).is_none());
+    assert!(Gradient::from_stops([]).is_none());
+}
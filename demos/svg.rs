@@ -0,0 +1,34 @@
+use mazes::kind;
+
+fn main() {
+    let kind = kind::Regular::new(20, 20);
+    let mut grid = mazes::Grid::new(kind, None, None).recursive_backtracker();
+    grid.set_start(grid.get(0, 0).unwrap());
+    grid.save_svg("regular", 20, 0.0);
+    grid.save_svg("regular_inset", 20, 0.15);
+
+    let kind = kind::Polar::new(12);
+    mazes::Grid::new(kind, None, None)
+        .recursive_backtracker()
+        .save_svg("circle_maze", 20);
+
+    let kind = kind::Hex::new(12, 12);
+    mazes::Grid::new(kind, None, None)
+        .recursive_backtracker()
+        .save_svg("hex_maze", 20);
+
+    let kind = kind::Triangle::new(12, 20);
+    mazes::Grid::new(kind, None, None)
+        .recursive_backtracker()
+        .save_svg("triangle_maze", 20);
+
+    let kind = kind::Weave::new(15, 15);
+    mazes::Grid::new(kind, None, None)
+        .recursive_backtracker()
+        .save_svg("weave", 25, 0.1);
+
+    let kind = kind::ThreeD::new(5, 5, 3);
+    mazes::Grid::new(kind, None, None)
+        .recursive_backtracker()
+        .save_svg("3d", 20, 0.0);
+}
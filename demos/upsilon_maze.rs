@@ -0,0 +1,42 @@
+use image::Rgb;
+use mazes::{cell, generators::Registry, kind, solvers, RenderOptions};
+
+fn main() {
+    // octagons have eight sides to open and squares four
+    let grid = mazes::Grid::new(kind::Upsilon::new(5, 5), None, None);
+    let octagon = grid.get(2, 2).unwrap();
+    let square = grid.get(2, 3).unwrap();
+    assert_eq!(grid.neighbours(octagon).count(), 8);
+    assert_eq!(grid.neighbours(square).count(), 4);
+
+    for generator in Registry::<kind::Upsilon>::new().iter() {
+        let kind = kind::Upsilon::new(12, 12);
+        let grid = mazes::Grid::new(kind, None, None).generate(generator, &mut rand::thread_rng());
+        assert!(grid.is_perfect() && grid.invalid_links().is_empty());
+    }
+
+    let kind = kind::Upsilon::new(15, 15);
+    let mut grid = mazes::Grid::new(kind, None, None).recursive_backtracker();
+    grid.save_png("upsilon_maze", 25);
+
+    grid.set_start(cell::UpsilonCell {
+        row: grid.num_rows() as isize / 2,
+        col: grid.num_cols() as isize / 2,
+    });
+    grid.save_png("upsilon_maze_colorized", 25);
+
+    grid.set_start(grid.get(0, 0).unwrap());
+    grid.set_goal(grid.get(14, 14).unwrap());
+    let steps = grid.distances().expect("goal to be set").cells().count();
+    let solution = grid.solve(&solvers::AStar).expect("goal to be reachable");
+    assert_eq!(solution.path.len(), steps);
+
+    let options = RenderOptions {
+        shade: false,
+        solution: Some(Rgb([220, 40, 40])),
+        markers: true,
+        ..RenderOptions::default()
+    };
+    grid.save_png_to("images/upsilon_solution.png", 25, 0.0, &options)
+        .expect("image to be saved");
+}
@@ -0,0 +1,70 @@
+use image::Rgb;
+use mazes::{
+    generators::Registry,
+    kind::{self, Heading, Wrapping},
+    solvers::{self, Hand, WallFollower},
+    Grid, RenderOptions,
+};
+
+fn check<K: Wrapping + Heading>(name: &str, kind: impl Fn() -> K) {
+    for generator in Registry::<K>::new().iter() {
+        let grid = Grid::new(kind(), None, None).generate(generator, &mut rand::thread_rng());
+        assert!(
+            grid.is_connected() && grid.invalid_links().is_empty(),
+            "{name} {}",
+            generator.name()
+        );
+    }
+
+    let mut grid = Grid::new(kind(), None, None).wilsons();
+    assert!(grid.is_perfect());
+    let crossings = grid
+        .neighbouring_cells()
+        .into_iter()
+        .filter(|(cell, other)| {
+            cell.row.abs_diff(other.row) + cell.col.abs_diff(other.col) != 1
+                && grid.are_linked(*cell, *other)
+        })
+        .count();
+    println!("{name}: {crossings} passages wrap around the edges");
+
+    // the shortest way round may cross an edge, which A* has to allow for
+    let (start, goal) = (grid.get(0, 0).unwrap(), grid.get(6, 10).unwrap());
+    grid.set_start(start);
+    grid.set_goal(goal);
+    let steps = grid.distances().expect("goal to be set").cells().count();
+    for solver in solvers::all::<K>().into_iter().chain([
+        Box::new(WallFollower(Hand::Left)) as Box<dyn solvers::Solver<K>>,
+        Box::new(WallFollower(Hand::Right)),
+    ]) {
+        let solution = grid.solve(solver.as_ref()).expect("goal to be reachable");
+        assert!(solution
+            .path
+            .windows(2)
+            .all(|pair| grid.are_linked(pair[0], pair[1])));
+        if solver.name() == "a_star" {
+            assert_eq!(solution.path.len(), steps);
+        }
+    }
+
+    let options = RenderOptions {
+        shade: false,
+        solution: Some(Rgb([40, 90, 220])),
+        markers: true,
+        ..RenderOptions::default()
+    };
+    grid.save_png_to(format!("images/{name}.png"), 25, 0.0, &options)
+        .expect("png to be saved");
+    grid.save_png(&format!("{name}_inset"), 25, 0.15);
+}
+
+fn main() {
+    check("cylinder", || kind::Cylinder::new(12, 20));
+    check("torus", || kind::Torus::new(12, 20));
+    check("mobius", || kind::MobiusStrip::new(12, 20));
+    check("klein", || kind::KleinBottle::new(12, 20));
+
+    // on a torus one cell wide, the cell above and below are the same one
+    let grid = Grid::new(kind::Torus::new(2, 1), None, None).kruskals();
+    assert!(grid.is_perfect());
+}
@@ -0,0 +1,40 @@
+use std::env;
+
+use mazes::{generators::RecursiveBacktracker, kind, RenderOptions};
+
+fn main() {
+    let kind = kind::Regular::new(10, 10);
+    let grid = mazes::Grid::new(kind, None, None).recursive_backtracker();
+    let options = RenderOptions::default();
+
+    let img = grid.to_image(10, 0.0, &options);
+    assert_eq!(img.dimensions(), (101, 101));
+
+    let mut png = Vec::new();
+    grid.write_png(&mut png, 10, 0.0, &options)
+        .expect("png to be encoded");
+    assert!(png.starts_with(b"\x89PNG"));
+
+    let mut svg = Vec::new();
+    grid.write_svg(&mut svg, 10, 0.0, &options)
+        .expect("svg to be written");
+    assert!(svg.starts_with(b"<svg"));
+
+    let dir = env::temp_dir();
+    grid.save_png_to(dir.join("mazes_writers.png"), 10, 0.0, &options)
+        .expect("png to be saved");
+    grid.save_svg_to(dir.join("mazes_writers.svg"), 10, 0.0, &options)
+        .expect("svg to be saved");
+
+    assert!(grid
+        .save_png_to(dir.join("missing").join("maze.png"), 10, 0.0, &options)
+        .is_err());
+
+    let kind = kind::Hex::new(8, 8);
+    let mut gif = Vec::new();
+    mazes::Grid::new(kind, None, None)
+        .replay(&RecursiveBacktracker, &mut rand::thread_rng())
+        .write_gif(&mut gif, 10, 0.0, &options)
+        .expect("gif to be encoded");
+    assert!(gif.starts_with(b"GIF89a"));
+}
@@ -0,0 +1,89 @@
+use std::{
+    fs::File,
+    io::{BufWriter, Write},
+    path::Path,
+};
+
+use image::{
+    codecs::gif::{GifEncoder, Repeat},
+    Delay, DynamicImage, Frame, Rgb, RgbImage,
+};
+use rustc_hash::FxHashMap;
+
+use crate::{
+    error::MazeError,
+    generators::{Event, Replay},
+    grid::{Grid, Render, Shading},
+    style::RenderOptions,
+};
+
+const CURRENT: Rgb<u8> = Rgb([255, 64, 64]);
+const ACTIVE: Rgb<u8> = Rgb([255, 200, 120]);
+const FRAME_DELAY_MS: u32 = 40;
+
+impl<K: Render> Replay<K> {
+    /// Plays the rest of the run into an animated GIF with one frame per cell visited and
+    /// passage carved or removed, so random walks show up as well as the passages they leave,
+    /// highlighting the current cell and the generator's active set. Returns the finished grid.
+    pub fn save_gif(
+        self,
+        path: impl AsRef<Path>,
+        cell_size: u32,
+        inset: f32,
+        options: &RenderOptions,
+    ) -> Result<Grid<K>, MazeError> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        let grid = self.write_gif(&mut writer, cell_size, inset, options)?;
+        writer.flush()?;
+
+        Ok(grid)
+    }
+
+    /// Same as [`Replay::save_gif`], but encoding into any writer.
+    pub fn write_gif(
+        mut self,
+        writer: impl Write,
+        cell_size: u32,
+        inset: f32,
+        options: &RenderOptions,
+    ) -> Result<Grid<K>, MazeError> {
+        let mut encoder = GifEncoder::new_with_speed(writer, 30);
+        encoder.set_repeat(Repeat::Infinite)?;
+
+        let mut current = None;
+        while let Some(event) = self.next() {
+            let highlighted = match event {
+                Event::Activated(_) | Event::Deactivated(_) => continue,
+                Event::Visited(cell) => *current.insert(cell),
+                Event::Linked(_, other) | Event::Unlinked(_, other) => current.unwrap_or(other),
+            };
+
+            let mut colors = FxHashMap::from_iter(self.frontier().map(|c| (c, ACTIVE)));
+            colors.insert(highlighted, CURRENT);
+
+            let img = K::render(
+                self.grid(),
+                Some(Shading::Highlight(colors)),
+                cell_size,
+                inset,
+                options,
+            );
+            encoder.encode_frame(frame(img))?;
+        }
+
+        let grid = self.finish();
+        let img = K::render(&grid, None, cell_size, inset, options);
+        encoder.encode_frame(frame(img))?;
+
+        Ok(grid)
+    }
+}
+
+fn frame(img: RgbImage) -> Frame {
+    Frame::from_parts(
+        DynamicImage::ImageRgb8(img).into_rgba8(),
+        0,
+        0,
+        Delay::from_numer_denom_ms(FRAME_DELAY_MS, 1),
+    )
+}
@@ -0,0 +1,303 @@
+use std::fmt::Write;
+
+use image::{Rgb, RgbImage};
+use imageproc::{
+    drawing::{
+        draw_antialiased_line_segment_mut, draw_filled_circle_mut, draw_filled_rect_mut,
+        draw_hollow_circle_mut, draw_polygon_mut,
+    },
+    pixelops,
+    point::Point,
+    rect::Rect,
+};
+
+use crate::style::RenderOptions;
+
+/// A surface that grids can be drawn onto, either raster or vector.
+///
+/// Coordinates are relative to the maze, so the canvas is responsible for the outer margin,
+/// and walls are drawn in the colour and thickness of the [`RenderOptions`] it was made with.
+pub(crate) trait Canvas {
+    type Output;
+
+    fn new(width: u32, height: u32, options: &RenderOptions) -> Self;
+    fn finish(self) -> Self::Output;
+
+    fn wall(&mut self, from: (i32, i32), to: (i32, i32));
+    fn wall_circle(&mut self, center: (i32, i32), radius: i32);
+    /// A one pixel line, for markings that aren't walls.
+    fn line(&mut self, from: (i32, i32), to: (i32, i32), color: Rgb<u8>);
+    fn rect(&mut self, rect: Rect, color: Rgb<u8>);
+    fn polygon(&mut self, points: &[Point<i32>], color: Rgb<u8>);
+    fn polyline(&mut self, points: &[(i32, i32)], color: Rgb<u8>, thickness: u32);
+    fn circle(&mut self, center: (i32, i32), radius: i32, color: Rgb<u8>);
+}
+
+pub(crate) struct Raster {
+    img: RgbImage,
+    margin: i32,
+    wall: Rgb<u8>,
+    wall_thickness: u32,
+}
+
+impl Raster {
+    fn stroke(&mut self, from: (i32, i32), to: (i32, i32), color: Rgb<u8>, thickness: u32) {
+        if thickness <= 1 {
+            self.line(from, to, color);
+            return;
+        }
+
+        // a quad around the segment with round caps, so that strokes meeting at a corner join up
+        let half = thickness as f32 / 2.0;
+        let (x1, y1) = self.offset_f32(from);
+        let (x2, y2) = self.offset_f32(to);
+        let length = (x2 - x1).hypot(y2 - y1);
+        if length > 0.0 {
+            let (nx, ny) = (-(y2 - y1) / length * half, (x2 - x1) / length * half);
+            let corner = |x: f32, y: f32| Point::new(x.round() as i32, y.round() as i32);
+            let quad = [
+                corner(x1 + nx, y1 + ny),
+                corner(x2 + nx, y2 + ny),
+                corner(x2 - nx, y2 - ny),
+                corner(x1 - nx, y1 - ny),
+            ];
+            if quad[0] != quad[3] {
+                draw_polygon_mut(&mut self.img, &quad, color);
+            }
+        }
+
+        let radius = (half - 0.5).round() as i32;
+        for end in [from, to] {
+            let end = self.offset(end);
+            draw_filled_circle_mut(&mut self.img, end, radius, color);
+        }
+    }
+
+    fn offset(&self, (x, y): (i32, i32)) -> (i32, i32) {
+        (x + self.margin, y + self.margin)
+    }
+
+    fn offset_f32(&self, (x, y): (i32, i32)) -> (f32, f32) {
+        let (x, y) = self.offset((x, y));
+        (x as f32, y as f32)
+    }
+}
+
+impl Canvas for Raster {
+    type Output = RgbImage;
+
+    fn new(width: u32, height: u32, options: &RenderOptions) -> Self {
+        let margin = options.margin;
+
+        Self {
+            img: RgbImage::from_pixel(width + 2 * margin, height + 2 * margin, options.background),
+            margin: margin as i32,
+            wall: options.wall,
+            wall_thickness: options.wall_thickness,
+        }
+    }
+
+    fn finish(self) -> RgbImage {
+        self.img
+    }
+
+    fn wall(&mut self, from: (i32, i32), to: (i32, i32)) {
+        self.stroke(from, to, self.wall, self.wall_thickness);
+    }
+
+    fn wall_circle(&mut self, center: (i32, i32), radius: i32) {
+        let center = self.offset(center);
+        let half = self.wall_thickness as i32 / 2;
+        for r in radius - half..radius - half + self.wall_thickness.max(1) as i32 {
+            draw_hollow_circle_mut(&mut self.img, center, r, self.wall);
+        }
+    }
+
+    fn line(&mut self, from: (i32, i32), to: (i32, i32), color: Rgb<u8>) {
+        let (from, to) = (self.offset(from), self.offset(to));
+        draw_antialiased_line_segment_mut(&mut self.img, from, to, color, pixelops::interpolate);
+    }
+
+    fn rect(&mut self, rect: Rect, color: Rgb<u8>) {
+        let (left, top) = self.offset((rect.left(), rect.top()));
+        let rect = Rect::at(left, top).of_size(rect.width(), rect.height());
+        draw_filled_rect_mut(&mut self.img, rect, color);
+    }
+
+    fn polygon(&mut self, points: &[Point<i32>], color: Rgb<u8>) {
+        let points = points
+            .iter()
+            .map(|p| {
+                let (x, y) = self.offset((p.x, p.y));
+                Point::new(x, y)
+            })
+            .collect::<Vec<_>>();
+        draw_polygon_mut(&mut self.img, &points, color);
+    }
+
+    fn polyline(&mut self, points: &[(i32, i32)], color: Rgb<u8>, thickness: u32) {
+        for pair in points.windows(2) {
+            self.stroke(pair[0], pair[1], color, thickness);
+        }
+    }
+
+    fn circle(&mut self, center: (i32, i32), radius: i32, color: Rgb<u8>) {
+        let center = self.offset(center);
+        draw_filled_circle_mut(&mut self.img, center, radius, color);
+    }
+}
+
+/// An SVG document built up shape by shape, with runs of same-coloured lines merged into a
+/// single path.
+pub(crate) struct Svg {
+    width: u32,
+    height: u32,
+    wall: Rgb<u8>,
+    wall_thickness: u32,
+    body: String,
+    path: Option<(Rgb<u8>, u32, String)>,
+}
+
+impl Svg {
+    fn flush_path(&mut self) {
+        if let Some((color, width, d)) = self.path.take() {
+            writeln!(
+                self.body,
+                "<path d=\"{d}\" fill=\"none\" stroke=\"{}\" stroke-width=\"{width}\" \
+                 stroke-linecap=\"round\"/>",
+                hex(color)
+            )
+            .expect("writing to a string to succeed");
+        }
+    }
+
+    fn shape(&mut self, shape: &str) {
+        self.flush_path();
+        self.body.push_str(shape);
+        self.body.push('\n');
+    }
+
+    fn stroke(&mut self, (x1, y1): (i32, i32), (x2, y2): (i32, i32), color: Rgb<u8>, width: u32) {
+        if self
+            .path
+            .as_ref()
+            .is_some_and(|(c, w, _)| (*c, *w) != (color, width))
+        {
+            self.flush_path();
+        }
+
+        let (_, _, d) = self
+            .path
+            .get_or_insert_with(|| (color, width, String::new()));
+        if !d.is_empty() {
+            d.push(' ');
+        }
+        write!(d, "M{x1} {y1}L{x2} {y2}").expect("writing to a string to succeed");
+    }
+}
+
+impl Canvas for Svg {
+    type Output = String;
+
+    fn new(width: u32, height: u32, options: &RenderOptions) -> Self {
+        let mut svg = Self {
+            width: width + 2 * options.margin,
+            height: height + 2 * options.margin,
+            wall: options.wall,
+            wall_thickness: options.wall_thickness,
+            body: String::new(),
+            path: None,
+        };
+        svg.shape(&format!(
+            "<rect width=\"{}\" height=\"{}\" fill=\"{}\"/>",
+            svg.width,
+            svg.height,
+            hex(options.background)
+        ));
+        svg.shape(&format!(
+            "<g transform=\"translate({margin} {margin})\">",
+            margin = options.margin
+        ));
+
+        svg
+    }
+
+    fn finish(mut self) -> String {
+        self.flush_path();
+
+        let Self {
+            width,
+            height,
+            body,
+            ..
+        } = self;
+        format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{width}\" height=\"{height}\" \
+             viewBox=\"0 0 {width} {height}\">\n{body}</g>\n</svg>\n"
+        )
+    }
+
+    fn wall(&mut self, from: (i32, i32), to: (i32, i32)) {
+        self.stroke(from, to, self.wall, self.wall_thickness);
+    }
+
+    fn wall_circle(&mut self, (cx, cy): (i32, i32), radius: i32) {
+        self.shape(&format!(
+            "<circle cx=\"{cx}\" cy=\"{cy}\" r=\"{radius}\" fill=\"none\" stroke=\"{}\" \
+             stroke-width=\"{}\"/>",
+            hex(self.wall),
+            self.wall_thickness
+        ));
+    }
+
+    fn line(&mut self, from: (i32, i32), to: (i32, i32), color: Rgb<u8>) {
+        self.stroke(from, to, color, 1);
+    }
+
+    fn rect(&mut self, rect: Rect, color: Rgb<u8>) {
+        self.shape(&format!(
+            "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"{}\"/>",
+            rect.left(),
+            rect.top(),
+            rect.width(),
+            rect.height(),
+            hex(color)
+        ));
+    }
+
+    fn polygon(&mut self, points: &[Point<i32>], color: Rgb<u8>) {
+        let points = points
+            .iter()
+            .map(|p| format!("{},{}", p.x, p.y))
+            .collect::<Vec<_>>()
+            .join(" ");
+        self.shape(&format!(
+            "<polygon points=\"{points}\" fill=\"{}\"/>",
+            hex(color)
+        ));
+    }
+
+    fn polyline(&mut self, points: &[(i32, i32)], color: Rgb<u8>, thickness: u32) {
+        let points = points
+            .iter()
+            .map(|(x, y)| format!("{x},{y}"))
+            .collect::<Vec<_>>()
+            .join(" ");
+        self.shape(&format!(
+            "<polyline points=\"{points}\" fill=\"none\" stroke=\"{}\" \
+             stroke-width=\"{thickness}\" stroke-linecap=\"round\" stroke-linejoin=\"round\"/>",
+            hex(color)
+        ));
+    }
+
+    fn circle(&mut self, (cx, cy): (i32, i32), radius: i32, color: Rgb<u8>) {
+        self.shape(&format!(
+            "<circle cx=\"{cx}\" cy=\"{cy}\" r=\"{radius}\" fill=\"{}\"/>",
+            hex(color)
+        ));
+    }
+}
+
+fn hex(Rgb([r, g, b]): Rgb<u8>) -> String {
+    format!("#{r:02x}{g:02x}{b:02x}")
+}
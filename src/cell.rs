@@ -65,6 +65,25 @@ impl TriangleCell {
     }
 }
 
+/// A cell of an [`Upsilon`](crate::kind::Upsilon) grid, which alternates between octagons and
+/// squares like a checkerboard.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct UpsilonCell {
+    pub row: isize,
+    pub col: isize,
+}
+
+impl UpsilonCell {
+    pub fn new(row: isize, col: isize) -> Self {
+        Self { row, col }
+    }
+
+    /// Octagons sit where the row and column add up to an even number, with squares between.
+    pub fn is_octagon(&self) -> bool {
+        (self.row + self.col) % 2 == 0
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct WeightedCell {
     pub row: isize,
@@ -93,13 +112,6 @@ impl WeaveCell {
         Self::Over(OverCell::new(row, col))
     }
 
-    pub fn col(&self) -> isize {
-        match self {
-            Self::Over(o) => o.col,
-            Self::Under(u) => u.over.col,
-        }
-    }
-
     /// Returns `true` if the weave cell is [`Over`].
     ///
     /// [`Over`]: WeaveCell::Over
@@ -145,11 +157,86 @@ impl ThreeDCell {
     }
 }
 
+/// A cell on one of the six faces of a [`Cube`](crate::kind::Cube), by its row and column as
+/// drawn on the face in the unfolded net.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct CubeCell {
+    pub face: isize,
+    pub row: isize,
+    pub col: isize,
+}
+
+/// Each face's outward normal, then the ways its columns and rows run, in x, y, z with x to
+/// the right, y up and z towards the viewer. Faces are numbered top, left, front, right, back
+/// and bottom, and the columns and rows run right and down as seen from outside the cube.
+const FACES: [[[isize; 3]; 3]; 6] = [
+    [[0, 1, 0], [1, 0, 0], [0, 0, 1]],
+    [[-1, 0, 0], [0, 0, 1], [0, -1, 0]],
+    [[0, 0, 1], [1, 0, 0], [0, -1, 0]],
+    [[1, 0, 0], [0, 0, -1], [0, -1, 0]],
+    [[0, 0, -1], [-1, 0, 0], [0, -1, 0]],
+    [[0, -1, 0], [1, 0, 0], [0, 0, -1]],
+];
+
+fn dot(a: [isize; 3], b: [isize; 3]) -> isize {
+    a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
+}
+
+impl CubeCell {
+    pub fn new(face: isize, row: isize, col: isize) -> Self {
+        Self { face, row, col }
+    }
+
+    /// The centre of the cell in space, for a cube `size` cells across centred on the origin,
+    /// measured in half cells so that it stays whole.
+    pub fn position(&self, size: usize) -> [isize; 3] {
+        let [normal, across, down] = FACES[self.face as usize];
+        let size = size as isize;
+        let a = 2 * self.col - size + 1;
+        let b = 2 * self.row - size + 1;
+
+        [0, 1, 2].map(|axis| size * normal[axis] + a * across[axis] + b * down[axis])
+    }
+
+    /// The cell whose centre is at `position`, the inverse of [`CubeCell::position`].
+    pub fn at(position: [isize; 3], size: usize) -> Option<Self> {
+        let size = size as isize;
+        let face = FACES
+            .iter()
+            .position(|[normal, ..]| dot(*normal, position) == size)?;
+        let [_, across, down] = FACES[face];
+        let col = (dot(across, position) + size - 1) / 2;
+        let row = (dot(down, position) + size - 1) / 2;
+
+        ((0..size).contains(&row) && (0..size).contains(&col))
+            .then(|| Self::new(face as isize, row, col))
+    }
+
+    /// The cell one step from this one along its face's columns (`across`) or rows (`down`),
+    /// turning over the edge onto the next face when it runs off this one.
+    pub fn step(&self, size: usize, across: isize, down: isize) -> Option<Self> {
+        let [normal, across_axis, down_axis] = FACES[self.face as usize];
+        let position = self.position(size);
+        let direction = [0, 1, 2].map(|axis| across * across_axis[axis] + down * down_axis[axis]);
+
+        // centres on the same face are two half cells apart, and ones either side of an edge are
+        // half a cell from it along each face
+        let on_face = [0, 1, 2].map(|axis| position[axis] + 2 * direction[axis]);
+        let over_edge = [0, 1, 2].map(|axis| position[axis] + direction[axis] - normal[axis]);
+
+        Self::at(on_face, size).or_else(|| Self::at(over_edge, size))
+    }
+}
+
 pub trait CellKind
 where
     Self: std::fmt::Debug + Clone + Copy + PartialEq + Eq + PartialOrd + Ord + std::hash::Hash,
 {
     fn row(&self) -> isize;
+    fn col(&self) -> isize;
+    fn level(&self) -> isize {
+        0
+    }
     fn weight(&self) -> usize {
         1
     }
@@ -159,30 +246,54 @@ impl CellKind for RegularCell {
     fn row(&self) -> isize {
         self.row
     }
+    fn col(&self) -> isize {
+        self.col
+    }
 }
 
 impl CellKind for PolarCell {
     fn row(&self) -> isize {
         self.row
     }
+    fn col(&self) -> isize {
+        self.col
+    }
 }
 
 impl CellKind for HexCell {
     fn row(&self) -> isize {
         self.row
     }
+    fn col(&self) -> isize {
+        self.col
+    }
 }
 
 impl CellKind for TriangleCell {
     fn row(&self) -> isize {
         self.row
     }
+    fn col(&self) -> isize {
+        self.col
+    }
+}
+
+impl CellKind for UpsilonCell {
+    fn row(&self) -> isize {
+        self.row
+    }
+    fn col(&self) -> isize {
+        self.col
+    }
 }
 
 impl CellKind for WeightedCell {
     fn row(&self) -> isize {
         self.row
     }
+    fn col(&self) -> isize {
+        self.col
+    }
 
     fn weight(&self) -> usize {
         self.weight
@@ -196,10 +307,88 @@ impl CellKind for WeaveCell {
             Self::Under(u) => u.over.row,
         }
     }
+    fn col(&self) -> isize {
+        match self {
+            Self::Over(o) => o.col,
+            Self::Under(u) => u.over.col,
+        }
+    }
 }
 
 impl CellKind for ThreeDCell {
     fn row(&self) -> isize {
         self.row
     }
+    fn col(&self) -> isize {
+        self.col
+    }
+    fn level(&self) -> isize {
+        self.level
+    }
+}
+
+impl CellKind for CubeCell {
+    fn row(&self) -> isize {
+        self.row
+    }
+    fn col(&self) -> isize {
+        self.col
+    }
+    /// The face, so that cells are looked up face by face like the levels of a 3D grid.
+    fn level(&self) -> isize {
+        self.face
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rustc_hash::{FxHashMap, FxHashSet};
+
+    use super::*;
+
+    fn cube_cells(size: usize) -> impl Iterator<Item = CubeCell> {
+        let size = size as isize;
+        (0..6).flat_map(move |face| {
+            (0..size).flat_map(move |row| (0..size).map(move |col| CubeCell::new(face, row, col)))
+        })
+    }
+
+    fn steps(cell: CubeCell, size: usize) -> Vec<CubeCell> {
+        [(0, -1), (1, 0), (0, 1), (-1, 0)]
+            .into_iter()
+            .map(|(across, down)| {
+                cell.step(size, across, down)
+                    .expect("every cell to have four neighbours")
+            })
+            .collect()
+    }
+
+    #[test]
+    fn cube_cells_have_four_neighbours_each_way() {
+        for size in 1..=4 {
+            let mut across_edges = FxHashMap::default();
+
+            for cell in cube_cells(size) {
+                assert_eq!(CubeCell::at(cell.position(size), size), Some(cell));
+
+                let neighbours = steps(cell, size);
+                let distinct = neighbours.iter().collect::<FxHashSet<_>>();
+                assert_eq!(distinct.len(), 4, "{cell:?} on a cube of {size}");
+                assert!(!distinct.contains(&cell));
+
+                for other in neighbours {
+                    assert!(steps(other, size).contains(&cell), "{cell:?} to {other:?}");
+                    if other.face != cell.face {
+                        let faces = (cell.face.min(other.face), cell.face.max(other.face));
+                        *across_edges.entry(faces).or_insert(0) += 1;
+                    }
+                }
+            }
+
+            // every face meets the four beside it and never the one opposite, along a whole edge
+            // of cells that each count the step both ways
+            assert_eq!(across_edges.len(), 12, "cube of {size}");
+            assert!(across_edges.values().all(|steps| *steps == 2 * size));
+        }
+    }
 }
@@ -0,0 +1,213 @@
+use std::ops::Range;
+
+use rand::{seq::index, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+
+use crate::{
+    cell::RegularCell,
+    generators::{Generator, Kruskals},
+    grid::Grid,
+    kind::Regular,
+};
+
+#[derive(Clone, Copy)]
+enum Border {
+    East = 1,
+    South = 2,
+    Inside = 3,
+}
+
+/// An endless plane of mazes cut into chunks that can be generated one at a time and in any
+/// order. The openings in a border only depend on the seed and where the border is, so the
+/// chunks on either side agree on them.
+#[derive(Debug, Clone)]
+pub struct Chunks {
+    seed: u64,
+    rows: usize,
+    cols: usize,
+    openings: usize,
+}
+
+/// One chunk of [`Chunks`], with the columns open to the north and south and the rows open to
+/// the west and east.
+pub struct Chunk {
+    pub grid: Grid<Regular>,
+    pub north: Vec<usize>,
+    pub south: Vec<usize>,
+    pub west: Vec<usize>,
+    pub east: Vec<usize>,
+}
+
+impl Chunks {
+    pub fn new(seed: u64, rows: usize, cols: usize) -> Self {
+        Self {
+            seed,
+            rows,
+            cols,
+            openings: 1,
+        }
+    }
+
+    /// How many openings each border has, at least one and one by default.
+    pub fn openings(mut self, openings: usize) -> Self {
+        self.openings = openings.max(1);
+        self
+    }
+
+    /// The chunk at `row` and `col`, generated with Kruskal's algorithm.
+    pub fn chunk(&self, row: isize, col: isize) -> Chunk {
+        self.chunk_with(row, col, &Kruskals)
+    }
+
+    pub fn chunk_with(
+        &self,
+        row: isize,
+        col: isize,
+        generator: &(impl Generator<Regular> + ?Sized),
+    ) -> Chunk {
+        let grid = Grid::new(Regular::new(self.rows, self.cols), None, None)
+            .generate(generator, &mut self.rng(row, col, Border::Inside));
+
+        Chunk {
+            grid,
+            north: self.border(row - 1, col, Border::South),
+            south: self.border(row, col, Border::South),
+            west: self.border(row, col - 1, Border::East),
+            east: self.border(row, col, Border::East),
+        }
+    }
+
+    /// The chunks in `rows` and `cols` joined up into one grid through their openings.
+    pub fn stitch(
+        &self,
+        rows: Range<isize>,
+        cols: Range<isize>,
+        generator: &(impl Generator<Regular> + ?Sized),
+    ) -> Grid<Regular> {
+        let (height, width) = (self.rows as isize, self.cols as isize);
+        let kind = Regular::new(rows.len() * self.rows, cols.len() * self.cols);
+        let mut grid = Grid::new(kind, None, None);
+
+        for (i, row) in rows.clone().enumerate() {
+            for (j, col) in cols.clone().enumerate() {
+                let chunk = self.chunk_with(row, col, generator);
+                let (top, left) = (i as isize * height, j as isize * width);
+                let at = |cell: RegularCell| RegularCell::new(top + cell.row, left + cell.col);
+
+                for cell in chunk.grid.cells() {
+                    for other in chunk.grid.links(cell) {
+                        grid.link(at(cell), at(other));
+                    }
+                }
+                if row + 1 < rows.end {
+                    for c in chunk.south {
+                        let cell = at(RegularCell::new(height - 1, c as isize));
+                        grid.link(cell, RegularCell::new(cell.row + 1, cell.col));
+                    }
+                }
+                if col + 1 < cols.end {
+                    for r in chunk.east {
+                        let cell = at(RegularCell::new(r as isize, width - 1));
+                        grid.link(cell, RegularCell::new(cell.row, cell.col + 1));
+                    }
+                }
+            }
+        }
+
+        grid
+    }
+
+    fn border(&self, row: isize, col: isize, border: Border) -> Vec<usize> {
+        let length = match border {
+            Border::East => self.rows,
+            _ => self.cols,
+        };
+
+        let mut openings = index::sample(
+            &mut self.rng(row, col, border),
+            length,
+            self.openings.min(length),
+        )
+        .into_vec();
+        openings.sort_unstable();
+        openings
+    }
+
+    /// ChaCha rather than `StdRng`, whose output may change between versions and platforms.
+    fn rng(&self, row: isize, col: isize, border: Border) -> ChaCha8Rng {
+        let hash = [row as u64, col as u64, border as u64]
+            .into_iter()
+            .fold(self.seed, |hash, part| mix(hash ^ mix(part)));
+
+        let mut seed = [0; 32];
+        for (i, bytes) in seed.chunks_exact_mut(8).enumerate() {
+            bytes.copy_from_slice(&mix(hash ^ i as u64).to_le_bytes());
+        }
+        ChaCha8Rng::from_seed(seed)
+    }
+}
+
+/// The SplitMix64 finaliser.
+fn mix(mut x: u64) -> u64 {
+    x = x.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    x = (x ^ (x >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    x = (x ^ (x >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    x ^ (x >> 31)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn chunks_are_pinned_to_their_seed() {
+        let chunk = Chunks::new(42, 4, 5).openings(2).chunk(-1, 3);
+
+        assert_eq!(
+            chunk.grid.to_string().lines().collect::<Vec<_>>(),
+            [
+                "┌───┬───────────────┐",
+                "│   │               │",
+                "│   ╵   ╶───┐   ╷   │",
+                "│           │   │   │",
+                "│   ┌───╴   │   │   │",
+                "│   │       │   │   │",
+                "│   │   ╶───┴───┴───┤",
+                "│   │               │",
+                "└───┴───────────────┘",
+            ]
+        );
+        assert_eq!(
+            [chunk.north, chunk.south, chunk.west, chunk.east],
+            [vec![2, 4], vec![2, 4], vec![2, 3], vec![0, 3]]
+        );
+    }
+
+    #[test]
+    fn neighbouring_chunks_agree_on_their_borders() {
+        let chunks = Chunks::new(7, 5, 6).openings(3);
+
+        for (row, col) in [(0, 0), (-3, 8), (12, -5)] {
+            let chunk = chunks.chunk(row, col);
+            assert_eq!(chunk.east, chunks.chunk(row, col + 1).west);
+            assert_eq!(chunk.south, chunks.chunk(row + 1, col).north);
+            assert_eq!(chunk.east.len(), 3);
+            assert_eq!(
+                chunk.grid.to_string(),
+                chunks.chunk(row, col).grid.to_string()
+            );
+        }
+        assert_ne!(
+            Chunks::new(8, 5, 6).chunk(0, 0).grid.to_string(),
+            Chunks::new(7, 5, 6).chunk(0, 0).grid.to_string()
+        );
+    }
+
+    #[test]
+    fn stitched_chunks_are_connected() {
+        let grid = Chunks::new(3, 4, 4).stitch(-1..2, 0..3, &Kruskals);
+
+        assert_eq!(grid.size(), 3 * 3 * 4 * 4);
+        assert!(grid.is_connected());
+    }
+}
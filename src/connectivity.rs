@@ -0,0 +1,145 @@
+use std::collections::VecDeque;
+
+use rustc_hash::FxHashMap;
+
+use crate::{grid::Grid, kind::Kind};
+
+impl<K: Kind> Grid<K> {
+    /// The groups of cells that can reach each other through passages.
+    pub fn components(&self) -> Vec<Vec<K::Cell>> {
+        let mut component_of = FxHashMap::default();
+        let mut components = Vec::new();
+
+        for cell in self.cells() {
+            if component_of.contains_key(&cell) {
+                continue;
+            }
+
+            let id = components.len();
+            let mut component = Vec::new();
+            let mut pending = VecDeque::from([cell]);
+            component_of.insert(cell, id);
+            while let Some(cell) = pending.pop_front() {
+                component.push(cell);
+                for other in self.links(cell) {
+                    if component_of.insert(other, id).is_none() {
+                        pending.push_back(other);
+                    }
+                }
+            }
+            components.push(component);
+        }
+
+        components
+    }
+
+    pub fn is_connected(&self) -> bool {
+        self.components().len() <= 1
+    }
+
+    /// Whether there is exactly one route between any two cells: connected, with no loops.
+    pub fn is_perfect(&self) -> bool {
+        self.is_connected() && self.cycles().is_empty()
+    }
+
+    /// A basis of the loops in the maze: one cycle for each passage that closes a loop in a
+    /// breadth-first spanning forest, given as the cells around it. Every loop in the maze can be
+    /// made by combining these.
+    pub fn cycles(&self) -> Vec<Vec<K::Cell>> {
+        // parent and depth of each cell in the spanning forest
+        let mut tree = FxHashMap::default();
+        let mut cycles = Vec::new();
+
+        for root in self.cells() {
+            if tree.contains_key(&root) {
+                continue;
+            }
+
+            tree.insert(root, (None, 0));
+            let mut pending = VecDeque::from([root]);
+            while let Some(cell) = pending.pop_front() {
+                let (parent, depth) = tree[&cell];
+                for other in self.links(cell) {
+                    match tree.get(&other) {
+                        None => {
+                            tree.insert(other, (Some(cell), depth + 1));
+                            pending.push_back(other);
+                        }
+                        // each passage is seen from both ends, so only close the loop from one
+                        Some(_) if Some(other) != parent && cell < other => {
+                            cycles.push(Self::cycle_through(&tree, cell, other));
+                        }
+                        Some(_) => {}
+                    }
+                }
+            }
+        }
+
+        cycles
+    }
+
+    /// The loop made by adding the passage between `cell` and `other` to the spanning forest.
+    fn cycle_through(
+        tree: &FxHashMap<K::Cell, (Option<K::Cell>, usize)>,
+        cell: K::Cell,
+        other: K::Cell,
+    ) -> Vec<K::Cell> {
+        let climb = |cell| {
+            tree[&cell]
+                .0
+                .expect("a cell below the root to have a parent")
+        };
+
+        let (mut left, mut right) = (vec![cell], vec![other]);
+        let (mut a, mut b) = (cell, other);
+        while tree[&a].1 > tree[&b].1 {
+            a = climb(a);
+            left.push(a);
+        }
+        while tree[&b].1 > tree[&a].1 {
+            b = climb(b);
+            right.push(b);
+        }
+        while a != b {
+            a = climb(a);
+            b = climb(b);
+            left.push(a);
+            right.push(b);
+        }
+
+        // both sides end at the common ancestor
+        right.pop();
+        left.extend(right.into_iter().rev());
+        left
+    }
+
+    /// Passages that join cells which aren't neighbours for this kind of grid, which a correct
+    /// generator never makes.
+    pub fn invalid_links(&self) -> Vec<(K::Cell, K::Cell)> {
+        self.cells()
+            .into_iter()
+            .flat_map(|cell| {
+                self.links(cell)
+                    .filter(move |other| cell < *other)
+                    .map(move |other| (cell, other))
+            })
+            .filter(|(cell, other)| !K::is_valid_link(self, *cell, *other))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{cell::TriangleCell, grid::Grid, kind::Triangle};
+
+    #[test]
+    fn triangles_only_link_across_a_shared_side() {
+        let mut grid = Grid::new(Triangle::new(2, 2), None, None);
+        let cell = |row, col| TriangleCell::new(row, col);
+        // (0, 0) points up onto (1, 0) below it, while (0, 1) and (1, 1) only meet at a tip
+        grid.link(cell(0, 0), cell(1, 0));
+        grid.link(cell(0, 1), cell(1, 1));
+
+        assert_eq!(grid.invalid_links(), [(cell(0, 1), cell(1, 1))]);
+    }
+}
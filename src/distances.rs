@@ -25,22 +25,26 @@ impl<K: Kind> Distances<K> {
         self.distances.insert(cell, distance);
     }
 
-    pub fn path_to(&self, goal: K::Cell, grid: &Grid<K>) -> Self {
+    /// The cells on the way back from `goal` to the root, or `None` if the goal can't be reached.
+    /// Each step back goes to the closest linked cell, which on weighted grids is the cheapest
+    /// way in rather than just any cell nearer the root.
+    pub fn path_to(&self, goal: K::Cell, grid: &Grid<K>) -> Option<Self> {
         let mut current = goal;
 
         let mut breadcrumbs = Self::new(self.root);
-        breadcrumbs.insert(current, self[current]);
+        breadcrumbs.insert(current, self.get(&current)?);
 
         while current != self.root {
-            let closer = grid
+            let (closer, distance) = grid
                 .links(current)
-                .find(|linked| self[*linked] < self[current])
-                .expect("at least one link to the current cell should be closer to the root");
-            breadcrumbs.insert(closer, self[closer]);
+                .filter_map(|linked| Some((linked, self.get(&linked)?)))
+                .filter(|(_, distance)| *distance < self[current])
+                .min_by_key(|(_, distance)| *distance)?;
+            breadcrumbs.insert(closer, distance);
             current = closer;
         }
 
-        breadcrumbs
+        Some(breadcrumbs)
     }
 
     pub fn max(&self) -> (K::Cell, usize) {
@@ -63,3 +67,46 @@ impl<K: Kind> ops::Index<K::Cell> for Distances<K> {
         &self.distances[&index]
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        cell::RegularCell,
+        grid::Grid,
+        kind::{Regular, Weighted},
+        path::Path,
+    };
+
+    #[test]
+    fn unreachable_goals_have_no_path() {
+        let (start, goal) = (RegularCell::new(0, 0), RegularCell::new(0, 2));
+        let mut grid = Grid::new(Regular::new(1, 3), Some(start), Some(goal));
+        grid.link(start, RegularCell::new(0, 1));
+
+        assert!(grid.distances_from(start).path_to(goal, &grid).is_none());
+        assert!(grid.path().is_none());
+    }
+
+    #[test]
+    fn weighted_paths_cost_what_their_distances_say() {
+        let mut grid = Grid::new(Weighted::new(2, 2), None, None);
+        grid.set_weight(0, 1, 5);
+        grid.set_weight(1, 1, 10);
+        let cell = |row, col| grid.get(row, col).expect("cell to be in the grid");
+        let (start, dear, cheap, goal) = (cell(0, 0), cell(0, 1), cell(1, 0), cell(1, 1));
+        // the dearer way in is linked first, so it is the first closer cell found
+        for (cell, other) in [(goal, dear), (goal, cheap), (start, dear), (start, cheap)] {
+            grid.link(cell, other);
+        }
+
+        let distances = grid.distances_from(start);
+        let path = Path::from(
+            distances
+                .path_to(goal, &grid)
+                .expect("goal to be reachable"),
+        );
+
+        assert_eq!(path.cells(), [start, cheap, goal]);
+        assert_eq!(path.cost(), distances[goal]);
+    }
+}
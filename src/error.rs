@@ -0,0 +1,85 @@
+use core::fmt;
+use std::{error, io};
+
+use image::ImageError;
+
+/// Why a fallible operation on a maze failed, for callers that take their input from users and
+/// can't afford to panic.
+#[derive(Debug)]
+pub enum MazeError {
+    Io(io::Error),
+    Image(ImageError),
+    /// A mask file with no rows or no columns.
+    EmptyMask,
+    /// A row of a mask file with a different length from the first.
+    UnevenMask {
+        row: usize,
+        expected: usize,
+        found: usize,
+    },
+    /// A random cell was asked of a grid without any.
+    EmptyGrid,
+    /// A grid with cells that no passage can reach from the others, so no maze joins them all.
+    DisconnectedGrid,
+    /// A position that has no cell in the grid, either out of bounds or masked off.
+    CellNotFound {
+        row: isize,
+        col: isize,
+    },
+    /// A saved maze that doesn't describe a valid grid.
+    InvalidMaze(String),
+    #[cfg(feature = "serde")]
+    Json(serde_json::Error),
+}
+
+impl fmt::Display for MazeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(err) => write!(f, "{err}"),
+            Self::Image(err) => write!(f, "{err}"),
+            Self::EmptyMask => write!(f, "mask has no cells"),
+            Self::UnevenMask {
+                row,
+                expected,
+                found,
+            } => write!(f, "mask row {row} has {found} columns, expected {expected}"),
+            Self::EmptyGrid => write!(f, "grid has no cells"),
+            Self::DisconnectedGrid => write!(f, "grid has cells cut off from the others"),
+            Self::CellNotFound { row, col } => write!(f, "no cell at row {row}, column {col}"),
+            Self::InvalidMaze(reason) => write!(f, "invalid maze: {reason}"),
+            #[cfg(feature = "serde")]
+            Self::Json(err) => write!(f, "{err}"),
+        }
+    }
+}
+
+impl error::Error for MazeError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            Self::Io(err) => Some(err),
+            Self::Image(err) => Some(err),
+            #[cfg(feature = "serde")]
+            Self::Json(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for MazeError {
+    fn from(err: io::Error) -> Self {
+        Self::Io(err)
+    }
+}
+
+impl From<ImageError> for MazeError {
+    fn from(err: ImageError) -> Self {
+        Self::Image(err)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl From<serde_json::Error> for MazeError {
+    fn from(err: serde_json::Error) -> Self {
+        Self::Json(err)
+    }
+}
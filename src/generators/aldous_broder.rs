@@ -1,26 +1,46 @@
-use rand::seq::IteratorRandom;
+use rand::{seq::IteratorRandom, Rng, RngCore};
 
-use crate::{grid::Grid, kind::Kind};
+use crate::{
+    generators::{Event, Generator},
+    grid::Grid,
+    kind::Kind,
+};
 
-impl<K: Kind> Grid<K> {
-    pub fn aldous_broder(mut self) -> Self {
-        let mut cell = self.get_random_cell();
-        let mut unvisited = self.size() - 1;
+pub struct AldousBroder;
+
+impl<K: Kind> Generator<K> for AldousBroder {
+    fn name(&self) -> &'static str {
+        "aldous_broder"
+    }
+
+    fn generate(&self, grid: &mut Grid<K>, rng: &mut dyn RngCore) {
+        let mut cell = grid.get_random_cell_with_rng(rng);
+        grid.record(|| Event::Visited(cell));
+        let mut unvisited = grid.size() - 1;
 
         while unvisited > 0 {
-            let neighbour = self
+            let neighbour = grid
                 .neighbours(cell)
-                .choose(&mut rand::thread_rng())
+                .choose(rng)
                 .expect("neighbours should be non-empty");
 
-            if self.links(neighbour).next().is_none() {
-                self.link(cell, neighbour);
+            if grid.links(neighbour).next().is_none() {
+                grid.link(cell, neighbour);
                 unvisited -= 1;
             }
 
             cell = neighbour;
+            grid.record(|| Event::Visited(cell));
         }
+    }
+}
+
+impl<K: Kind> Grid<K> {
+    pub fn aldous_broder(self) -> Self {
+        self.aldous_broder_with_rng(&mut rand::thread_rng())
+    }
 
-        self
+    pub fn aldous_broder_with_rng(self, rng: &mut impl Rng) -> Self {
+        self.generate(&AldousBroder, rng)
     }
 }
@@ -1,76 +1,118 @@
-use rand::Rng;
+use rand::{Rng, RngCore};
 
 use crate::{
+    generators::{Event, Generator},
     grid::Grid,
     kind::{Hex, Regular, ThreeD},
 };
 
-impl Grid<Regular> {
-    pub fn binary_tree(mut self) -> Self {
-        for cell in self.cells() {
-            match (self.north(cell), self.east(cell)) {
+pub struct BinaryTree;
+
+impl Generator<Regular> for BinaryTree {
+    fn name(&self) -> &'static str {
+        "binary_tree"
+    }
+
+    fn generate(&self, grid: &mut Grid<Regular>, rng: &mut dyn RngCore) {
+        for cell in grid.cells() {
+            grid.record(|| Event::Visited(cell));
+            match (grid.north(cell), grid.east(cell)) {
                 (None, None) => {}
-                (None, Some(other)) | (Some(other), None) => self.link(cell, other),
+                (None, Some(other)) | (Some(other), None) => grid.link(cell, other),
                 (Some(north), Some(east)) => {
-                    if rand::random() {
-                        self.link(cell, north);
+                    if rng.gen() {
+                        grid.link(cell, north);
                     } else {
-                        self.link(cell, east);
+                        grid.link(cell, east);
                     }
                 }
             }
         }
-
-        self
     }
 }
 
-impl Grid<Hex> {
-    pub fn binary_tree(mut self) -> Self {
-        for cell in self.cells() {
-            match (self.north(cell), self.get_next_in_row(cell)) {
+impl Generator<Hex> for BinaryTree {
+    fn name(&self) -> &'static str {
+        "binary_tree"
+    }
+
+    fn generate(&self, grid: &mut Grid<Hex>, rng: &mut dyn RngCore) {
+        for cell in grid.cells() {
+            grid.record(|| Event::Visited(cell));
+            match (grid.north(cell), grid.get_next_in_row(cell)) {
                 (None, None) => {}
-                (None, Some(other)) | (Some(other), None) => self.link(cell, other),
+                (None, Some(other)) | (Some(other), None) => grid.link(cell, other),
                 (Some(north), Some(east)) => {
-                    if rand::random() {
-                        self.link(cell, north);
+                    if rng.gen() {
+                        grid.link(cell, north);
                     } else {
-                        self.link(cell, east);
+                        grid.link(cell, east);
                     }
                 }
             }
         }
-
-        self
     }
 }
 
-impl Grid<ThreeD> {
-    pub fn binary_tree(mut self) -> Self {
-        for cell in self.cells() {
-            match (self.north(cell), self.east(cell), self.up(cell)) {
+impl Generator<ThreeD> for BinaryTree {
+    fn name(&self) -> &'static str {
+        "binary_tree"
+    }
+
+    fn generate(&self, grid: &mut Grid<ThreeD>, rng: &mut dyn RngCore) {
+        for cell in grid.cells() {
+            grid.record(|| Event::Visited(cell));
+            match (grid.north(cell), grid.east(cell), grid.up(cell)) {
                 (None, None, None) => {}
                 (None, Some(other), None)
                 | (Some(other), None, None)
-                | (None, None, Some(other)) => self.link(cell, other),
+                | (None, None, Some(other)) => grid.link(cell, other),
                 (None, Some(first), Some(second))
                 | (Some(first), None, Some(second))
                 | (Some(first), Some(second), None) => {
-                    if rand::random() {
-                        self.link(cell, first);
+                    if rng.gen() {
+                        grid.link(cell, first);
                     } else {
-                        self.link(cell, second);
+                        grid.link(cell, second);
                     }
                 }
-                (Some(north), Some(east), Some(up)) => match rand::thread_rng().gen_range(0..3) {
-                    0 => self.link(cell, north),
-                    1 => self.link(cell, east),
-                    2 => self.link(cell, up),
+                (Some(north), Some(east), Some(up)) => match rng.gen_range(0..3) {
+                    0 => grid.link(cell, north),
+                    1 => grid.link(cell, east),
+                    2 => grid.link(cell, up),
                     _ => unreachable!(),
                 },
             }
         }
+    }
+}
+
+impl Grid<Regular> {
+    pub fn binary_tree(self) -> Self {
+        self.binary_tree_with_rng(&mut rand::thread_rng())
+    }
+
+    pub fn binary_tree_with_rng(self, rng: &mut impl Rng) -> Self {
+        self.generate(&BinaryTree, rng)
+    }
+}
+
+impl Grid<Hex> {
+    pub fn binary_tree(self) -> Self {
+        self.binary_tree_with_rng(&mut rand::thread_rng())
+    }
+
+    pub fn binary_tree_with_rng(self, rng: &mut impl Rng) -> Self {
+        self.generate(&BinaryTree, rng)
+    }
+}
+
+impl Grid<ThreeD> {
+    pub fn binary_tree(self) -> Self {
+        self.binary_tree_with_rng(&mut rand::thread_rng())
+    }
 
-        self
+    pub fn binary_tree_with_rng(self, rng: &mut impl Rng) -> Self {
+        self.generate(&BinaryTree, rng)
     }
 }
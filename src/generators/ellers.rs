@@ -1,18 +1,26 @@
-use rand::{seq::SliceRandom, Rng};
+use std::{fmt, hash::Hash};
+
+use petgraph::unionfind::UnionFind;
+use rand::{seq::SliceRandom, Rng, RngCore};
 use rustc_hash::FxHashMap;
 
-use crate::{cell::RegularCell, grid::Grid, kind::Regular};
+use crate::{
+    cell::RegularCell,
+    generators::{Event, Generator},
+    grid::Grid,
+    kind::{Hex, Kind, Masked, Regular, ThreeD, Weighted},
+};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 struct SetId(usize);
 
-struct RowState {
-    set_for_cell: FxHashMap<RegularCell, SetId>,
-    cells_in_set: FxHashMap<SetId, Vec<RegularCell>>,
+struct RowState<C> {
+    set_for_cell: FxHashMap<C, SetId>,
+    cells_in_set: FxHashMap<SetId, Vec<C>>,
     next_set: SetId,
 }
 
-impl RowState {
+impl<C: Copy + Eq + Hash> RowState<C> {
     fn new(next_set: SetId) -> Self {
         Self {
             set_for_cell: FxHashMap::default(),
@@ -21,13 +29,13 @@ impl RowState {
         }
     }
 
-    fn record(&mut self, set: SetId, cell: RegularCell) {
+    fn record(&mut self, set: SetId, cell: C) {
         self.set_for_cell.insert(cell, set);
         self.cells_in_set.entry(set).or_default().push(cell);
     }
 
-    fn set_for(&mut self, cell: RegularCell) -> SetId {
-        if self.set_for_cell.get(&cell).is_none() {
+    fn set_for(&mut self, cell: C) -> SetId {
+        if !self.set_for_cell.contains_key(&cell) {
             self.record(self.next_set, cell);
             self.next_set = SetId(self.next_set.0 + 1);
         }
@@ -54,50 +62,446 @@ impl RowState {
         Self::new(self.next_set)
     }
 
-    fn each_set(&self) -> impl Iterator<Item = (&SetId, &Vec<RegularCell>)> {
+    fn each_set(&self) -> impl Iterator<Item = (&SetId, &Vec<C>)> {
         self.cells_in_set.iter()
     }
 }
 
-impl Grid<Regular> {
-    pub fn ellers(mut self) -> Self {
-        let mut row_state = RowState::new(SetId(0));
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum Node {
+    Set(SetId),
+    Region(usize),
+}
 
-        for row in self.rows() {
-            for &cell in &row {
-                if let Some(west) = self.west(cell) {
-                    let set = row_state.set_for(cell);
-                    let prior_set = row_state.set_for(west);
+enum Spare<C> {
+    Across(C, C),
+    Down(C, C),
+}
+
+/// Eller's algorithm over any grid cut into rows, which may have gaps. Each row joins its sets
+/// across the row or into a region they share below, so none is cut off.
+fn carve<K: Kind>(
+    grid: &mut Grid<K>,
+    rows: &[Vec<K::Cell>],
+    before: impl Fn(&Grid<K>, K::Cell) -> Vec<K::Cell>,
+    below: impl Fn(&Grid<K>, K::Cell) -> Option<K::Cell>,
+    region: impl Fn(usize, K::Cell) -> usize,
+    rng: &mut dyn RngCore,
+) {
+    let mut row_state = RowState::new(SetId(0));
+
+    for (idx, row) in rows.iter().enumerate() {
+        let mut spares = Vec::new();
+        for &cell in row {
+            grid.record(|| Event::Visited(cell));
+            row_state.set_for(cell);
+
+            for other in before(grid, cell) {
+                let set = row_state.set_for(cell);
+                let prior_set = row_state.set_for(other);
+                if set == prior_set {
+                    continue;
+                }
+
+                if rng.gen() {
+                    grid.link(cell, other);
+                    row_state.merge(prior_set, set);
+                } else {
+                    spares.push(Spare::Across(cell, other));
+                }
+            }
+        }
+
+        let mut nodes = FxHashMap::default();
+        let mut node = |node: Node| {
+            let next = nodes.len();
+            *nodes.entry(node).or_insert(next)
+        };
+        let mut joined = UnionFind::new(2 * row.len());
+
+        let mut downs = Vec::new();
+        for (set, list) in row_state.each_set() {
+            let mut list = list
+                .iter()
+                .filter_map(|cell| below(grid, *cell).map(|down| (*cell, down)))
+                .collect::<Vec<_>>();
+            list.shuffle(rng);
+
+            for (i, (cell, down)) in list.into_iter().enumerate() {
+                if i == 0 || rng.gen_range(0..3) == 0 {
+                    grid.link(cell, down);
+                    joined.union(
+                        node(Node::Set(*set)),
+                        node(Node::Region(region(idx + 1, down))),
+                    );
+                    downs.push((cell, down));
+                } else {
+                    spares.push(Spare::Down(cell, down));
+                }
+            }
+        }
 
-                    let should_link =
-                        set != prior_set && (self.south(cell).is_none() || rand::random());
-                    if should_link {
-                        self.link(cell, west);
+        // open whichever spare passages it takes to join up the sets that can't otherwise meet
+        spares.shuffle(rng);
+        for spare in spares {
+            match spare {
+                Spare::Across(cell, other) => {
+                    let set = row_state.set_for(cell);
+                    let prior_set = row_state.set_for(other);
+                    if joined.union(node(Node::Set(prior_set)), node(Node::Set(set))) {
+                        grid.link(cell, other);
                         row_state.merge(prior_set, set);
                     }
                 }
+                Spare::Down(cell, down) => {
+                    let set = row_state.set_for(cell);
+                    let below = node(Node::Region(region(idx + 1, down)));
+                    if joined.union(node(Node::Set(set)), below) {
+                        grid.link(cell, down);
+                        downs.push((cell, down));
+                    }
+                }
             }
+        }
 
-            if self.south(row[0]).is_some() {
-                let mut next_row = row_state.next();
+        let mut next_row = row_state.next();
+        for (cell, down) in downs {
+            next_row.record(row_state.set_for(cell), down);
+        }
+        row_state = next_row;
+    }
+}
 
-                for (_, list) in row_state.each_set() {
-                    let mut list = list.clone();
-                    list.shuffle(&mut rand::thread_rng());
+/// Labels the cells of each row by which of them connect through that row and the ones below.
+/// A row's regions depend on every row below it, so they're worked out for the whole grid up
+/// front, bottom to top, taking a label per cell.
+fn regions<K: Kind>(
+    grid: &Grid<K>,
+    rows: &[Vec<K::Cell>],
+    before: impl Fn(&Grid<K>, K::Cell) -> Vec<K::Cell>,
+    below: impl Fn(&Grid<K>, K::Cell) -> Option<K::Cell>,
+) -> Vec<FxHashMap<K::Cell, usize>> {
+    let mut regions = vec![FxHashMap::default(); rows.len()];
 
-                    for (idx, cell) in list.into_iter().enumerate() {
-                        if idx == 0 || rand::thread_rng().gen_range(0..3) == 0 {
-                            let south = self.south(cell).expect("south to exist");
-                            self.link(cell, south);
-                            next_row.record(row_state.set_for_cell[&cell], south);
-                        }
-                    }
+    for (idx, row) in rows.iter().enumerate().rev() {
+        let position = row
+            .iter()
+            .enumerate()
+            .map(|(i, cell)| (*cell, i))
+            .collect::<FxHashMap<_, _>>();
+        let mut connected = UnionFind::new(row.len());
+        let mut through = FxHashMap::default();
+
+        for (i, cell) in row.iter().enumerate() {
+            for other in before(grid, *cell) {
+                connected.union(i, position[&other]);
+            }
+            if let Some(down) = below(grid, *cell) {
+                let region = regions[idx + 1][&down];
+                connected.union(i, *through.entry(region).or_insert(i));
+            }
+        }
+
+        regions[idx] = row
+            .iter()
+            .enumerate()
+            .map(|(i, cell)| (*cell, connected.find(i)))
+            .collect();
+    }
+
+    regions
+}
+
+pub struct Ellers;
+
+impl Generator<Regular> for Ellers {
+    fn name(&self) -> &'static str {
+        "ellers"
+    }
+
+    fn generate(&self, grid: &mut Grid<Regular>, rng: &mut dyn RngCore) {
+        let rows = grid.rows();
+        let west = |grid: &Grid<Regular>, cell| grid.west(cell).into_iter().collect();
+
+        carve(grid, &rows, west, Grid::<Regular>::south, |_, _| 0, rng);
+    }
+}
+
+impl Generator<Weighted> for Ellers {
+    fn name(&self) -> &'static str {
+        "ellers"
+    }
+
+    fn generate(&self, grid: &mut Grid<Weighted>, rng: &mut dyn RngCore) {
+        let rows = (0..grid.num_rows() as isize)
+            .map(|row| {
+                (0..grid.num_cols() as isize)
+                    .filter_map(|col| grid.get(row, col))
+                    .collect()
+            })
+            .collect::<Vec<_>>();
+        let west = |grid: &Grid<Weighted>, cell| grid.west(cell).into_iter().collect();
+
+        carve(grid, &rows, west, Grid::<Weighted>::south, |_, _| 0, rng);
+    }
+}
+
+impl Generator<Masked> for Ellers {
+    fn name(&self) -> &'static str {
+        "ellers"
+    }
+
+    fn generate(&self, grid: &mut Grid<Masked>, rng: &mut dyn RngCore) {
+        let rows = (0..grid.num_rows() as isize)
+            .map(|row| {
+                (0..grid.num_cols() as isize)
+                    .filter_map(|col| grid.get(row, col))
+                    .collect()
+            })
+            .collect::<Vec<_>>();
+        let west = |grid: &Grid<Masked>, cell| grid.west(cell).into_iter().collect();
+
+        let regions = regions(grid, &rows, west, Grid::<Masked>::south);
+        carve(
+            grid,
+            &rows,
+            west,
+            Grid::<Masked>::south,
+            |row, cell| regions[row][&cell],
+            rng,
+        );
+    }
+}
+
+impl Generator<Hex> for Ellers {
+    fn name(&self) -> &'static str {
+        "ellers"
+    }
+
+    fn generate(&self, grid: &mut Grid<Hex>, rng: &mut dyn RngCore) {
+        let rows = grid.rows();
+        let previous = |grid: &Grid<Hex>, cell: <Hex as Kind>::Cell| {
+            grid.get(cell.row, cell.col - 1).into_iter().collect()
+        };
+
+        carve(grid, &rows, previous, Grid::<Hex>::south, |_, _| 0, rng);
+    }
+}
+
+impl Generator<ThreeD> for Ellers {
+    fn name(&self) -> &'static str {
+        "ellers"
+    }
+
+    fn generate(&self, grid: &mut Grid<ThreeD>, rng: &mut dyn RngCore) {
+        let levels = grid
+            .rows()
+            .chunks(grid.num_rows())
+            .map(<[_]>::concat)
+            .collect::<Vec<_>>();
+        let earlier = |grid: &Grid<ThreeD>, cell| {
+            [grid.west(cell), grid.north(cell)]
+                .into_iter()
+                .flatten()
+                .collect()
+        };
+
+        carve(grid, &levels, earlier, Grid::<ThreeD>::up, |_, _| 0, rng);
+    }
+}
+
+macro_rules! ellers_methods {
+    ($($T:ty),+ $(,)?) => {
+        $(
+            impl Grid<$T> {
+                pub fn ellers(self) -> Self {
+                    self.ellers_with_rng(&mut rand::thread_rng())
                 }
 
-                row_state = next_row;
+                pub fn ellers_with_rng(self, rng: &mut impl Rng) -> Self {
+                    self.generate(&Ellers, rng)
+                }
+            }
+        )+
+    };
+}
+
+ellers_methods!(Regular, Weighted, Masked, Hex, ThreeD);
+
+/// One row of a maze streamed by [`EllersRows`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EllersRow {
+    open_east: Vec<bool>,
+    open_south: Vec<bool>,
+}
+
+impl EllersRow {
+    pub fn num_cols(&self) -> usize {
+        self.open_east.len()
+    }
+
+    pub fn is_open_east(&self, col: usize) -> bool {
+        self.open_east[col]
+    }
+
+    pub fn is_open_south(&self, col: usize) -> bool {
+        self.open_south[col]
+    }
+}
+
+impl fmt::Display for EllersRow {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut cells = String::from("|");
+        let mut walls = String::from("+");
+        for col in 0..self.num_cols() {
+            cells.push_str(if self.open_east[col] { "    " } else { "   |" });
+            walls.push_str(if self.open_south[col] { "   +" } else { "---+" });
+        }
+
+        writeln!(f, "{cells}")?;
+        writeln!(f, "{walls}")
+    }
+}
+
+/// Eller's algorithm as an endless stream of rows, keeping only the current row's sets.
+pub struct EllersRows<R> {
+    sets: Vec<usize>,
+    rng: R,
+}
+
+impl<R: Rng> EllersRows<R> {
+    pub fn new(cols: usize, rng: R) -> Self {
+        Self {
+            sets: (0..cols).collect(),
+            rng,
+        }
+    }
+
+    /// The final row, joining up every set left open.
+    pub fn last_row(mut self) -> EllersRow {
+        let cols = self.sets.len();
+        let open_east = (0..cols)
+            .map(|col| col + 1 < cols && self.join(col))
+            .collect();
+
+        EllersRow {
+            open_east,
+            open_south: vec![false; cols],
+        }
+    }
+
+    fn join(&mut self, col: usize) -> bool {
+        let (set, next) = (self.sets[col], self.sets[col + 1]);
+        if set == next {
+            return false;
+        }
+
+        for other in &mut self.sets {
+            if *other == next {
+                *other = set;
+            }
+        }
+        true
+    }
+}
+
+impl<R: Rng> Iterator for EllersRows<R> {
+    type Item = EllersRow;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let cols = self.sets.len();
+        let open_east = (0..cols)
+            .map(|col| {
+                col + 1 < cols
+                    && self.sets[col] != self.sets[col + 1]
+                    && self.rng.gen()
+                    && self.join(col)
+            })
+            .collect();
+
+        let mut open_south = vec![false; cols];
+        let mut by_set = vec![Vec::new(); cols];
+        for (col, set) in self.sets.iter().enumerate() {
+            by_set[*set].push(col);
+        }
+        for mut list in by_set {
+            list.shuffle(&mut self.rng);
+            for (i, col) in list.into_iter().enumerate() {
+                open_south[col] = i == 0 || self.rng.gen_range(0..3) == 0;
+            }
+        }
+
+        // carry the sets that continue down, give the rest new ones, then renumber them all
+        let mut renumbered = vec![None; 2 * cols];
+        let mut next_set = 0;
+        for (col, set) in self.sets.iter_mut().enumerate() {
+            let carried = if open_south[col] { *set } else { cols + col };
+            *set = *renumbered[carried].get_or_insert_with(|| {
+                next_set += 1;
+                next_set - 1
+            });
+        }
+
+        Some(EllersRow {
+            open_east,
+            open_south,
+        })
+    }
+}
+
+impl Grid<Regular> {
+    /// Builds a grid from streamed rows, leaving out the passages south of the last one.
+    pub fn from_rows(rows: impl IntoIterator<Item = EllersRow>) -> Self {
+        let rows = rows.into_iter().collect::<Vec<_>>();
+        let cols = rows.first().map_or(0, EllersRow::num_cols);
+
+        let mut grid = Self::new(Regular::new(rows.len(), cols), None, None);
+        for (row, passages) in rows.iter().enumerate() {
+            for col in 0..cols {
+                let cell = RegularCell::new(row as isize, col as isize);
+                if passages.is_open_east(col) && col + 1 < cols {
+                    grid.link(cell, RegularCell::new(row as isize, col as isize + 1));
+                }
+                if passages.is_open_south(col) && row + 1 < rows.len() {
+                    grid.link(cell, RegularCell::new(row as isize + 1, col as isize));
+                }
             }
         }
 
-        self
+        grid
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use super::*;
+    use crate::mask::Mask;
+
+    fn perfect<K: Kind + Clone>(kind: &K)
+    where
+        Ellers: Generator<K>,
+    {
+        for seed in 0..8 {
+            let grid = Grid::new(kind.clone(), None, None)
+                .generate(&Ellers, &mut StdRng::seed_from_u64(seed));
+            assert!(grid.is_perfect(), "seed {seed}");
+        }
+    }
+
+    #[test]
+    fn ellers_makes_perfect_mazes_beyond_regular_grids() {
+        // rows split by gaps that only join up further down, and a row cut off from the one
+        // below except through its far end
+        let mask = [".X...X.", ".X.X.X.", "...X...", "XXXXXX.", "......."]
+            .join("\n")
+            .parse::<Mask>()
+            .expect("mask to parse");
+
+        perfect(&Hex::new(7, 6));
+        perfect(&Masked::new(mask));
+        perfect(&Weighted::new(6, 7));
+        perfect(&ThreeD::new(4, 4, 3));
     }
 }
@@ -1,20 +1,100 @@
-use rand::seq::IteratorRandom;
+use rand::{
+    seq::{IteratorRandom, SliceRandom},
+    Rng, RngCore,
+};
 
-use crate::{grid::Grid, kind::Kind};
+use crate::{
+    generators::{Event, Generator},
+    grid::Grid,
+    kind::Kind,
+};
 
-impl<K: Kind> Grid<K> {
-    pub fn growing_tree(mut self, choose: fn(&Vec<K::Cell>) -> Option<&K::Cell>) -> Self {
-        let start = self.get_random_cell();
+/// Growing tree with a strategy for choosing which active cell to grow from next.
+///
+/// [`GrowingTree::random`], [`GrowingTree::last`] and [`GrowingTree::mixed`] are the common
+/// strategies, and draw from the generator's rng so that they can be seeded and looked up in a
+/// [`Registry`](crate::generators::Registry).
+pub struct GrowingTree<K: Kind> {
+    name: &'static str,
+    strategy: Strategy<K>,
+}
+
+enum Strategy<K: Kind> {
+    Choose(fn(&Vec<K::Cell>) -> Option<&K::Cell>),
+    Random,
+    Last,
+    Mixed,
+}
+
+impl<K: Kind> GrowingTree<K> {
+    pub fn new(choose: fn(&Vec<K::Cell>) -> Option<&K::Cell>) -> Self {
+        Self {
+            name: "growing_tree",
+            strategy: Strategy::Choose(choose),
+        }
+    }
+
+    /// Grows from a random active cell, which makes mazes like simplified Prim's.
+    pub fn random() -> Self {
+        Self {
+            name: "growing_tree_random",
+            strategy: Strategy::Random,
+        }
+    }
+
+    /// Grows from the newest active cell, which makes mazes like the recursive backtracker.
+    pub fn last() -> Self {
+        Self {
+            name: "growing_tree_last",
+            strategy: Strategy::Last,
+        }
+    }
+
+    /// Grows from the newest or a random active cell, half the time each.
+    pub fn mixed() -> Self {
+        Self {
+            name: "growing_tree_mixed",
+            strategy: Strategy::Mixed,
+        }
+    }
+
+    // custom strategies take the list itself, as they always have
+    #[allow(clippy::ptr_arg)]
+    fn choose(&self, active: &Vec<K::Cell>, rng: &mut dyn RngCore) -> Option<K::Cell> {
+        match self.strategy {
+            Strategy::Choose(choose) => choose(active).copied(),
+            Strategy::Random => active.choose(rng).copied(),
+            Strategy::Last => active.last().copied(),
+            Strategy::Mixed => {
+                if rng.gen_bool(0.5) {
+                    active.last().copied()
+                } else {
+                    active.choose(rng).copied()
+                }
+            }
+        }
+    }
+}
+
+impl<K: Kind> Generator<K> for GrowingTree<K> {
+    fn name(&self) -> &'static str {
+        self.name
+    }
+
+    fn generate(&self, grid: &mut Grid<K>, rng: &mut dyn RngCore) {
+        let start = grid.get_random_cell_with_rng(rng);
         let mut active = vec![start];
+        grid.record(|| Event::Activated(start));
 
-        while let Some(&cell) = choose(&active) {
-            let available_neighbours = self
+        while let Some(cell) = self.choose(&active, rng) {
+            let available_neighbours = grid
                 .neighbours(cell)
-                .filter(|n| self.links(*n).next().is_none());
+                .filter(|n| grid.links(*n).next().is_none());
 
-            match available_neighbours.choose(&mut rand::thread_rng()) {
+            match available_neighbours.choose(rng) {
                 Some(neighbour) => {
-                    self.link(cell, neighbour);
+                    grid.link(cell, neighbour);
+                    grid.record(|| Event::Activated(neighbour));
                     active.push(neighbour);
                 }
                 None => {
@@ -23,10 +103,23 @@ impl<K: Kind> Grid<K> {
                         .position(|c| *c == cell)
                         .expect("cell to be found");
                     active.swap_remove(idx);
+                    grid.record(|| Event::Deactivated(cell));
                 }
             }
         }
+    }
+}
+
+impl<K: Kind> Grid<K> {
+    pub fn growing_tree(self, choose: fn(&Vec<K::Cell>) -> Option<&K::Cell>) -> Self {
+        self.growing_tree_with_rng(choose, &mut rand::thread_rng())
+    }
 
-        self
+    pub fn growing_tree_with_rng(
+        self,
+        choose: fn(&Vec<K::Cell>) -> Option<&K::Cell>,
+        rng: &mut impl Rng,
+    ) -> Self {
+        self.generate(&GrowingTree::new(choose), rng)
     }
 }
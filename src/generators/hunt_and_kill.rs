@@ -1,34 +1,43 @@
-use rand::seq::IteratorRandom;
+use rand::{seq::IteratorRandom, Rng, RngCore};
 
-use crate::{grid::Grid, kind::Kind};
+use crate::{
+    generators::{Event, Generator},
+    grid::Grid,
+    kind::Kind,
+};
 
-impl<K: Kind> Grid<K> {
-    pub fn hunt_and_kill(mut self) -> Self {
-        let mut current = Some(self.get_random_cell());
+pub struct HuntAndKill;
+
+impl<K: Kind> Generator<K> for HuntAndKill {
+    fn name(&self) -> &'static str {
+        "hunt_and_kill"
+    }
+
+    fn generate(&self, grid: &mut Grid<K>, rng: &mut dyn RngCore) {
+        let mut current = Some(grid.get_random_cell_with_rng(rng));
 
         while let Some(cell) = current {
-            let unvisited_neighbours = self
+            grid.record(|| Event::Visited(cell));
+            let unvisited_neighbours = grid
                 .neighbours(cell)
-                .filter(|n| self.links(*n).next().is_none());
+                .filter(|n| grid.links(*n).next().is_none());
 
-            match unvisited_neighbours.choose(&mut rand::thread_rng()) {
+            match unvisited_neighbours.choose(rng) {
                 Some(neighbour) => {
-                    self.link(cell, neighbour);
+                    grid.link(cell, neighbour);
                     current = Some(neighbour);
                 }
                 None => {
-                    current = self.cells().into_iter().find_map(|c| {
-                        if self.links(c).next().is_none() {
-                            let visited_neighbours = self
+                    current = grid.cells().into_iter().find_map(|c| {
+                        if grid.links(c).next().is_none() {
+                            let visited_neighbours = grid
                                 .neighbours(c)
-                                .filter(|n| self.links(*n).next().is_some());
-
-                            visited_neighbours
-                                .choose(&mut rand::thread_rng())
-                                .map(|neighbour| {
-                                    self.link(c, neighbour);
-                                    c
-                                })
+                                .filter(|n| grid.links(*n).next().is_some());
+
+                            visited_neighbours.choose(rng).map(|neighbour| {
+                                grid.link(c, neighbour);
+                                c
+                            })
                         } else {
                             None
                         }
@@ -36,7 +45,15 @@ impl<K: Kind> Grid<K> {
                 }
             }
         }
+    }
+}
+
+impl<K: Kind> Grid<K> {
+    pub fn hunt_and_kill(self) -> Self {
+        self.hunt_and_kill_with_rng(&mut rand::thread_rng())
+    }
 
-        self
+    pub fn hunt_and_kill_with_rng(self, rng: &mut impl Rng) -> Self {
+        self.generate(&HuntAndKill, rng)
     }
 }
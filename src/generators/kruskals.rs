@@ -1,8 +1,9 @@
-use rand::{seq::SliceRandom, Rng};
+use rand::{seq::SliceRandom, Rng, RngCore};
 use rustc_hash::FxHashMap;
 
 use crate::{
     cell::WeaveCell,
+    generators::Generator,
     grid::Grid,
     kind::{Kind, Weave},
 };
@@ -10,15 +11,15 @@ use crate::{
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 struct SetId(usize);
 
-struct State<K: Kind> {
-    grid: Grid<K>,
+struct State<'a, K: Kind> {
+    grid: &'a mut Grid<K>,
     neighbours: Vec<(K::Cell, K::Cell)>,
     set_for_cell: FxHashMap<K::Cell, SetId>,
     cells_in_set: FxHashMap<SetId, Vec<K::Cell>>,
 }
 
-impl<K: Kind> State<K> {
-    fn new(grid: Grid<K>) -> Self {
+impl<'a, K: Kind> State<'a, K> {
+    fn new(grid: &'a mut Grid<K>) -> Self {
         let neighbours = grid.neighbouring_cells();
         let mut set_for_cell = FxHashMap::default();
         let mut cells_in_set = FxHashMap::default();
@@ -65,8 +66,8 @@ impl<K: Kind> State<K> {
     }
 }
 
-impl State<Weave> {
-    fn add_crossing(&mut self, cell: WeaveCell) {
+impl State<'_, Weave> {
+    fn add_crossing(&mut self, cell: WeaveCell, rng: &mut dyn RngCore) {
         if let Some(((east, west), (north, south))) =
             (self.grid.east(cell).zip(self.grid.west(cell)))
                 .zip(self.grid.north(cell).zip(self.grid.south(cell)))
@@ -78,7 +79,7 @@ impl State<Weave> {
                 self.neighbours
                     .retain(|(left, right)| *left != cell && *right != cell);
 
-                if rand::random() {
+                if rng.gen() {
                     self.merge(west, cell);
                     self.merge(cell, east);
 
@@ -104,41 +105,78 @@ impl State<Weave> {
     }
 }
 
-impl<K: Kind> Grid<K> {
-    pub fn kruskals(self) -> Self {
-        let mut state = State::new(self);
-        state.neighbours.shuffle(&mut rand::thread_rng());
+pub struct Kruskals;
+
+impl<K: Kind> Generator<K> for Kruskals {
+    fn name(&self) -> &'static str {
+        "kruskals"
+    }
+
+    fn generate(&self, grid: &mut Grid<K>, rng: &mut dyn RngCore) {
+        let mut state = State::new(grid);
+        state.neighbours.shuffle(rng);
 
         while let Some((left, right)) = state.neighbours.pop() {
             if state.can_merge(left, right) {
                 state.merge(left, right);
             }
         }
-
-        state.grid
     }
 }
 
-impl Grid<Weave> {
-    pub fn kruskals_better_weave(mut self) -> Self {
-        self.get_kind_mut().is_preconfigured = true;
-
-        let mut state = State::new(self);
-        for _ in 0..state.grid.size() {
-            state.add_crossing(WeaveCell::new(
-                rand::thread_rng().gen_range(1..state.grid.num_rows() - 2) as isize,
-                rand::thread_rng().gen_range(1..state.grid.num_cols() - 2) as isize,
-            ));
+pub struct KruskalsBetterWeave;
+
+impl Generator<Weave> for KruskalsBetterWeave {
+    fn name(&self) -> &'static str {
+        "kruskals_better_weave"
+    }
+
+    fn generate(&self, grid: &mut Grid<Weave>, rng: &mut dyn RngCore) {
+        grid.get_kind_mut().is_preconfigured = true;
+
+        let mut state = State::new(grid);
+
+        // crossings need a cell on every side, so small grids go without
+        let rows = 1..state.grid.num_rows().saturating_sub(2);
+        let cols = 1..state.grid.num_cols().saturating_sub(2);
+        if !rows.is_empty() && !cols.is_empty() {
+            for _ in 0..state.grid.size() {
+                state.add_crossing(
+                    WeaveCell::new(
+                        rng.gen_range(rows.clone()) as isize,
+                        rng.gen_range(cols.clone()) as isize,
+                    ),
+                    rng,
+                );
+            }
         }
 
-        state.neighbours.shuffle(&mut rand::thread_rng());
+        state.neighbours.shuffle(rng);
 
         while let Some((left, right)) = state.neighbours.pop() {
             if state.can_merge(left, right) {
                 state.merge(left, right);
             }
         }
+    }
+}
+
+impl<K: Kind> Grid<K> {
+    pub fn kruskals(self) -> Self {
+        self.kruskals_with_rng(&mut rand::thread_rng())
+    }
+
+    pub fn kruskals_with_rng(self, rng: &mut impl Rng) -> Self {
+        self.generate(&Kruskals, rng)
+    }
+}
+
+impl Grid<Weave> {
+    pub fn kruskals_better_weave(self) -> Self {
+        self.kruskals_better_weave_with_rng(&mut rand::thread_rng())
+    }
 
-        state.grid
+    pub fn kruskals_better_weave_with_rng(self, rng: &mut impl Rng) -> Self {
+        self.generate(&KruskalsBetterWeave, rng)
     }
 }
@@ -7,5 +7,212 @@ mod kruskals;
 mod prims;
 mod recursive_backtracker;
 mod recursive_division;
+mod replay;
 mod sidewinder;
 mod wilsons;
+
+use std::collections::VecDeque;
+
+use rand::{Rng, RngCore};
+use rustc_hash::FxHashSet;
+
+use crate::{error::MazeError, grid::Grid, kind::Kind};
+
+pub use aldous_broder::AldousBroder;
+pub use binary_tree::BinaryTree;
+pub use ellers::{Ellers, EllersRow, EllersRows};
+pub use growing_tree::GrowingTree;
+pub use hunt_and_kill::HuntAndKill;
+pub use kruskals::{Kruskals, KruskalsBetterWeave};
+pub use prims::{SimplifiedPrims, TruePrims};
+pub use recursive_backtracker::RecursiveBacktracker;
+pub use recursive_division::RecursiveDivision;
+pub use replay::{Event, Replay};
+pub use sidewinder::Sidewinder;
+pub use wilsons::Wilsons;
+
+/// A maze generation algorithm that can carve passages into a [`Grid`] of kind `K`.
+///
+/// The kinds an algorithm supports are the kinds it is implemented for, and [`Kind::generators`]
+/// lists the ones available for each kind.
+pub trait Generator<K: Kind> {
+    fn name(&self) -> &'static str;
+    fn generate(&self, grid: &mut Grid<K>, rng: &mut dyn RngCore);
+}
+
+impl<K: Kind> Grid<K> {
+    /// Carves a maze with `generator`, which can panic or never finish on the grids
+    /// [`Grid::try_generate`] rejects.
+    pub fn generate(
+        mut self,
+        generator: &(impl Generator<K> + ?Sized),
+        rng: &mut impl Rng,
+    ) -> Self {
+        generator.generate(&mut self, rng);
+        self
+    }
+
+    /// Like [`Grid::generate`], but failing rather than panicking on grids no maze can be carved
+    /// through: ones with no cells, or with cells that no neighbour leads to, as a mask can
+    /// leave.
+    pub fn try_generate(
+        self,
+        generator: &(impl Generator<K> + ?Sized),
+        rng: &mut impl Rng,
+    ) -> Result<Self, MazeError> {
+        let cells = self.cells();
+        let first = *cells.first().ok_or(MazeError::EmptyGrid)?;
+
+        let mut reached = FxHashSet::from_iter([first]);
+        let mut pending = VecDeque::from([first]);
+        while let Some(cell) = pending.pop_front() {
+            for neighbour in self.neighbours(cell) {
+                if reached.insert(neighbour) {
+                    pending.push_back(neighbour);
+                }
+            }
+        }
+        if reached.len() < cells.len() {
+            return Err(MazeError::DisconnectedGrid);
+        }
+
+        Ok(self.generate(generator, rng))
+    }
+}
+
+/// Generators that work on any kind, since they only rely on [`Kind::neighbours`].
+pub(crate) fn generic<K: Kind>() -> Vec<Box<dyn Generator<K>>> {
+    vec![
+        Box::new(AldousBroder),
+        Box::new(Wilsons),
+        Box::new(HuntAndKill),
+        Box::new(RecursiveBacktracker),
+        Box::new(Kruskals),
+        Box::new(SimplifiedPrims),
+        Box::new(TruePrims),
+        Box::new(GrowingTree::random()),
+        Box::new(GrowingTree::last()),
+        Box::new(GrowingTree::mixed()),
+    ]
+}
+
+/// The generators available for a kind, looked up by name at runtime.
+pub struct Registry<K: Kind> {
+    generators: Vec<Box<dyn Generator<K>>>,
+}
+
+impl<K: Kind> Registry<K> {
+    pub fn new() -> Self {
+        Self {
+            generators: K::generators(),
+        }
+    }
+
+    pub fn register(&mut self, generator: impl Generator<K> + 'static) {
+        self.generators.push(Box::new(generator));
+    }
+
+    pub fn get(&self, name: &str) -> Option<&dyn Generator<K>> {
+        self.iter().find(|generator| generator.name() == name)
+    }
+
+    pub fn names(&self) -> impl Iterator<Item = &'static str> + '_ {
+        self.iter().map(|generator| generator.name())
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &dyn Generator<K>> {
+        self.generators.iter().map(|generator| generator.as_ref())
+    }
+}
+
+impl<K: Kind> Default for Registry<K> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use super::*;
+    use crate::{
+        kind::{Hex, Masked, Regular, Weave},
+        mask::Mask,
+    };
+
+    fn try_every<K: Kind + Clone>(kind: &K) -> Vec<Result<(), MazeError>> {
+        Registry::<K>::new()
+            .iter()
+            .map(|generator| {
+                Grid::new(kind.clone(), None, None)
+                    .try_generate(generator, &mut StdRng::seed_from_u64(0))
+                    .map(|grid| assert!(grid.is_connected(), "{}", generator.name()))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn try_generate_rejects_grids_without_a_maze() {
+        let hidden = "XXX\nXXX".parse::<Mask>().expect("mask to parse");
+        let mut islands = Mask::new(3, 3);
+        islands[0][1] = false;
+        islands[1][0] = false;
+
+        for result in [
+            try_every(&Regular::new(0, 0)),
+            try_every(&Hex::new(0, 4)),
+            try_every(&Masked::new(hidden)),
+        ]
+        .into_iter()
+        .flatten()
+        {
+            assert!(matches!(result, Err(MazeError::EmptyGrid)));
+        }
+        for result in try_every(&Masked::new(islands)) {
+            assert!(matches!(result, Err(MazeError::DisconnectedGrid)));
+        }
+    }
+
+    #[test]
+    fn try_generate_carves_the_smallest_grids() {
+        let mut ring = Mask::new(3, 3);
+        ring[1][1] = false;
+
+        for result in [
+            try_every(&Regular::new(1, 1)),
+            try_every(&Regular::new(1, 5)),
+            try_every(&Masked::new(ring)),
+            try_every(&Weave::new(1, 1)),
+            try_every(&Weave::new(2, 3)),
+        ]
+        .into_iter()
+        .flatten()
+        {
+            assert!(result.is_ok());
+        }
+    }
+
+    #[test]
+    fn growing_tree_presets_are_registered_and_seedable() {
+        let registry = Registry::<Regular>::new();
+
+        for name in [
+            "growing_tree_random",
+            "growing_tree_last",
+            "growing_tree_mixed",
+        ] {
+            let generator = registry.get(name).expect("preset to be registered");
+            let carve = |seed| {
+                Grid::new(Regular::new(6, 6), None, None)
+                    .generate(generator, &mut StdRng::seed_from_u64(seed))
+                    .to_string()
+            };
+
+            assert_eq!(carve(3), carve(3), "{name}");
+            assert!(Grid::new(Regular::new(6, 6), None, None)
+                .generate(generator, &mut StdRng::seed_from_u64(3))
+                .is_perfect());
+        }
+    }
+}
@@ -1,58 +1,96 @@
-use rand::{seq::IteratorRandom, Rng};
+use rand::{seq::IteratorRandom, Rng, RngCore};
 use rustc_hash::FxHashMap;
 
-use crate::{grid::Grid, kind::Kind};
+use crate::{
+    generators::{Event, Generator},
+    grid::Grid,
+    kind::Kind,
+};
 
-impl<K: Kind> Grid<K> {
-    pub fn simplified_prims(mut self) -> Self {
-        let start = self.get_random_cell();
+pub struct SimplifiedPrims;
+
+impl<K: Kind> Generator<K> for SimplifiedPrims {
+    fn name(&self) -> &'static str {
+        "simplified_prims"
+    }
+
+    fn generate(&self, grid: &mut Grid<K>, rng: &mut dyn RngCore) {
+        let start = grid.get_random_cell_with_rng(rng);
         let mut active = vec![start];
+        grid.record(|| Event::Activated(start));
 
-        while let Some((idx, &cell)) = active.iter().enumerate().choose(&mut rand::thread_rng()) {
-            let available_neighbours = self
+        while let Some((idx, &cell)) = active.iter().enumerate().choose(rng) {
+            let available_neighbours = grid
                 .neighbours(cell)
-                .filter(|n| self.links(*n).next().is_none());
+                .filter(|n| grid.links(*n).next().is_none());
 
-            match available_neighbours.choose(&mut rand::thread_rng()) {
+            match available_neighbours.choose(rng) {
                 Some(neighbour) => {
-                    self.link(cell, neighbour);
+                    grid.link(cell, neighbour);
+                    grid.record(|| Event::Activated(neighbour));
                     active.push(neighbour);
                 }
                 None => {
                     active.swap_remove(idx);
+                    grid.record(|| Event::Deactivated(cell));
                 }
             }
         }
+    }
+}
 
-        self
+pub struct TruePrims;
+
+impl<K: Kind> Generator<K> for TruePrims {
+    fn name(&self) -> &'static str {
+        "true_prims"
     }
 
-    pub fn true_prims(mut self) -> Self {
-        let start = self.get_random_cell();
+    fn generate(&self, grid: &mut Grid<K>, rng: &mut dyn RngCore) {
+        let start = grid.get_random_cell_with_rng(rng);
         let mut active = vec![start];
+        grid.record(|| Event::Activated(start));
 
         let costs = FxHashMap::from_iter(
-            self.cells()
+            grid.cells()
                 .into_iter()
-                .map(|cell| (cell, rand::thread_rng().gen_range(0..100))),
+                .map(|cell| (cell, rng.gen_range(0..100))),
         );
 
         while let Some((idx, &cell)) = active.iter().enumerate().min_by_key(|(_, c)| costs[c]) {
-            let available_neighbours = self
+            let available_neighbours = grid
                 .neighbours(cell)
-                .filter(|n| self.links(*n).next().is_none());
+                .filter(|n| grid.links(*n).next().is_none());
 
             match available_neighbours.min_by_key(|n| costs[n]) {
                 Some(neighbour) => {
-                    self.link(cell, neighbour);
+                    grid.link(cell, neighbour);
+                    grid.record(|| Event::Activated(neighbour));
                     active.push(neighbour);
                 }
                 None => {
                     active.swap_remove(idx);
+                    grid.record(|| Event::Deactivated(cell));
                 }
             }
         }
+    }
+}
+
+impl<K: Kind> Grid<K> {
+    pub fn simplified_prims(self) -> Self {
+        self.simplified_prims_with_rng(&mut rand::thread_rng())
+    }
+
+    pub fn simplified_prims_with_rng(self, rng: &mut impl Rng) -> Self {
+        self.generate(&SimplifiedPrims, rng)
+    }
+
+    pub fn true_prims(self) -> Self {
+        self.true_prims_with_rng(&mut rand::thread_rng())
+    }
 
-        self
+    pub fn true_prims_with_rng(self, rng: &mut impl Rng) -> Self {
+        self.generate(&TruePrims, rng)
     }
 }
@@ -1,27 +1,51 @@
-use rand::seq::IteratorRandom;
+use rand::{seq::IteratorRandom, Rng, RngCore};
 
-use crate::{grid::Grid, kind::Kind};
+use crate::{
+    generators::{Event, Generator},
+    grid::Grid,
+    kind::Kind,
+};
 
-impl<K: Kind> Grid<K> {
-    pub fn recursive_backtracker(mut self) -> Self {
-        let mut stack = vec![self.get_random_cell()];
+pub struct RecursiveBacktracker;
+
+impl<K: Kind> Generator<K> for RecursiveBacktracker {
+    fn name(&self) -> &'static str {
+        "recursive_backtracker"
+    }
+
+    fn generate(&self, grid: &mut Grid<K>, rng: &mut dyn RngCore) {
+        let mut stack = vec![grid.get_random_cell_with_rng(rng)];
+        grid.record(|| Event::Visited(stack[0]));
+        grid.record(|| Event::Activated(stack[0]));
 
         while let Some(current) = stack.last() {
-            let neighbours = self
+            let neighbours = grid
                 .neighbours(*current)
-                .filter(|n| self.links(*n).next().is_none());
+                .filter(|n| grid.links(*n).next().is_none());
 
-            match neighbours.choose(&mut rand::thread_rng()) {
+            match neighbours.choose(rng) {
                 Some(neighbour) => {
-                    self.link(*current, neighbour);
+                    grid.link(*current, neighbour);
+                    grid.record(|| Event::Visited(neighbour));
+                    grid.record(|| Event::Activated(neighbour));
                     stack.push(neighbour);
                 }
                 None => {
+                    let cell = *current;
                     stack.pop();
+                    grid.record(|| Event::Deactivated(cell));
                 }
             }
         }
+    }
+}
+
+impl<K: Kind> Grid<K> {
+    pub fn recursive_backtracker(self) -> Self {
+        self.recursive_backtracker_with_rng(&mut rand::thread_rng())
+    }
 
-        self
+    pub fn recursive_backtracker_with_rng(self, rng: &mut impl Rng) -> Self {
+        self.generate(&RecursiveBacktracker, rng)
     }
 }
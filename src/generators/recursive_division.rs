@@ -1,39 +1,64 @@
-use rand::Rng;
+use rand::{Rng, RngCore};
 
-use crate::{grid::Grid, kind::Regular};
+use crate::{generators::Generator, grid::Grid, kind::Regular};
 
-impl Grid<Regular> {
-    pub fn recursive_division(mut self) -> Self {
-        for cell in self.cells() {
-            self.neighbours(cell)
+pub struct RecursiveDivision;
+
+impl Generator<Regular> for RecursiveDivision {
+    fn name(&self) -> &'static str {
+        "recursive_division"
+    }
+
+    fn generate(&self, grid: &mut Grid<Regular>, rng: &mut dyn RngCore) {
+        for cell in grid.cells() {
+            grid.neighbours(cell)
                 .collect::<Vec<_>>()
                 .into_iter()
-                .for_each(|n| self.link(cell, n));
+                .for_each(|n| grid.link(cell, n));
         }
 
-        self.divide(0, 0, self.num_rows(), self.num_cols());
+        grid.divide(0, 0, grid.num_rows(), grid.num_cols(), rng);
+    }
+}
+
+impl Grid<Regular> {
+    pub fn recursive_division(self) -> Self {
+        self.recursive_division_with_rng(&mut rand::thread_rng())
+    }
 
-        self
+    pub fn recursive_division_with_rng(self, rng: &mut impl Rng) -> Self {
+        self.generate(&RecursiveDivision, rng)
     }
 
-    fn divide(&mut self, row: isize, col: isize, height: usize, width: usize) {
-        if height <= 1
-            || width <= 1
-            || height < 5 && width < 5 && rand::thread_rng().gen_range(0..4) == 0
-        {
+    fn divide(
+        &mut self,
+        row: isize,
+        col: isize,
+        height: usize,
+        width: usize,
+        rng: &mut dyn RngCore,
+    ) {
+        if height <= 1 || width <= 1 || height < 5 && width < 5 && rng.gen_range(0..4) == 0 {
             return;
         }
 
         if height > width {
-            self.divide_horizontally(row, col, height, width);
+            self.divide_horizontally(row, col, height, width, rng);
         } else {
-            self.divide_vertically(row, col, height, width);
+            self.divide_vertically(row, col, height, width, rng);
         }
     }
 
-    fn divide_horizontally(&mut self, row: isize, col: isize, height: usize, width: usize) {
-        let divide_south_of = rand::thread_rng().gen_range(0..height - 1);
-        let passage_at = rand::thread_rng().gen_range(0..width);
+    fn divide_horizontally(
+        &mut self,
+        row: isize,
+        col: isize,
+        height: usize,
+        width: usize,
+        rng: &mut dyn RngCore,
+    ) {
+        let divide_south_of = rng.gen_range(0..height - 1);
+        let passage_at = rng.gen_range(0..width);
 
         for x in 0..width {
             if passage_at != x {
@@ -45,18 +70,26 @@ impl Grid<Regular> {
             }
         }
 
-        self.divide(row, col, divide_south_of + 1, width);
+        self.divide(row, col, divide_south_of + 1, width, rng);
         self.divide(
             row + divide_south_of as isize + 1,
             col,
             height - divide_south_of - 1,
             width,
+            rng,
         );
     }
 
-    fn divide_vertically(&mut self, row: isize, col: isize, height: usize, width: usize) {
-        let divide_east_of = rand::thread_rng().gen_range(0..width - 1);
-        let passage_at = rand::thread_rng().gen_range(0..height);
+    fn divide_vertically(
+        &mut self,
+        row: isize,
+        col: isize,
+        height: usize,
+        width: usize,
+        rng: &mut dyn RngCore,
+    ) {
+        let divide_east_of = rng.gen_range(0..width - 1);
+        let passage_at = rng.gen_range(0..height);
 
         for y in 0..height {
             if passage_at != y {
@@ -68,12 +101,13 @@ impl Grid<Regular> {
             }
         }
 
-        self.divide(row, col, height, divide_east_of + 1);
+        self.divide(row, col, height, divide_east_of + 1, rng);
         self.divide(
             row,
             col + divide_east_of as isize + 1,
             height,
             width - divide_east_of - 1,
+            rng,
         );
     }
 }
@@ -0,0 +1,133 @@
+use std::vec;
+
+use rand::Rng;
+use rustc_hash::FxHashSet;
+
+use crate::{generators::Generator, grid::Grid, kind::Kind};
+
+/// Something a generator did while carving a maze.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Event<C> {
+    Linked(C, C),
+    Unlinked(C, C),
+    Visited(C),
+    /// A cell joined the generator's active set, such as the backtracker's stack or Prim's
+    /// frontier.
+    Activated(C),
+    /// A cell left the generator's active set.
+    Deactivated(C),
+}
+
+/// A recorded generator run, played back one event at a time.
+///
+/// [`Grid::replay`] runs the generator to completion before returning, so even a replay stopped
+/// after one event has cost the whole run. Its events are then applied to the starting grid as
+/// the iterator advances, so stopping early leaves a partially carved maze and exhausting it
+/// gives the same maze as the batch version with the same seed. Events only hold what changed,
+/// so a recording takes memory in proportion to the number of steps in the run.
+pub struct Replay<K: Kind> {
+    grid: Grid<K>,
+    frontier: FxHashSet<K::Cell>,
+    events: vec::IntoIter<Event<K::Cell>>,
+}
+
+impl<K: Kind> Replay<K> {
+    /// The grid as it is after the events yielded so far.
+    pub fn grid(&self) -> &Grid<K> {
+        &self.grid
+    }
+
+    /// The generator's active set after the events yielded so far, such as the backtracker's
+    /// stack or Prim's frontier, kept up to date from [`Event::Activated`] and
+    /// [`Event::Deactivated`].
+    pub fn frontier(&self) -> impl Iterator<Item = K::Cell> + '_ {
+        self.frontier.iter().copied()
+    }
+
+    /// Applies the remaining events and returns the finished grid.
+    pub fn finish(mut self) -> Grid<K> {
+        while self.next().is_some() {}
+        self.grid
+    }
+}
+
+impl<K: Kind> Iterator for Replay<K> {
+    type Item = Event<K::Cell>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let event = self.events.next()?;
+
+        match event {
+            Event::Linked(cell, other) => self.grid.connect(cell, other),
+            Event::Unlinked(cell, other) => self.grid.unlink(cell, other),
+            Event::Activated(cell) => {
+                self.frontier.insert(cell);
+            }
+            Event::Deactivated(cell) => {
+                self.frontier.remove(&cell);
+            }
+            Event::Visited(_) => {}
+        }
+
+        Some(event)
+    }
+}
+
+impl<K: Kind + Clone> Grid<K> {
+    /// Runs `generator` to completion while recording what it does, for playing back one
+    /// event at a time.
+    pub fn replay(self, generator: &(impl Generator<K> + ?Sized), rng: &mut impl Rng) -> Replay<K> {
+        let mut grid = self.clone();
+        grid.start_recording();
+        generator.generate(&mut grid, rng);
+
+        // generators may reconfigure the kind, so play the events back against the final one
+        let mut initial = self;
+        std::mem::swap(initial.get_kind_mut(), grid.get_kind_mut());
+
+        Replay {
+            grid: initial,
+            frontier: FxHashSet::default(),
+            events: grid.take_events().into_iter(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use super::*;
+    use crate::{generators::Registry, kind::Regular};
+
+    fn links<K: Kind>(grid: &Grid<K>) -> FxHashSet<(K::Cell, K::Cell)> {
+        grid.cells()
+            .into_iter()
+            .flat_map(|cell| grid.links(cell).map(move |other| (cell, other)))
+            .collect()
+    }
+
+    #[test]
+    fn playback_reproduces_the_generated_maze() {
+        for generator in Registry::<Regular>::new().iter() {
+            for seed in 0..4 {
+                let new = || Grid::new(Regular::new(9, 7), None, None);
+                let batch = new().generate(generator, &mut StdRng::seed_from_u64(seed));
+
+                let mut replay = new().replay(generator, &mut StdRng::seed_from_u64(seed));
+                let mut active = FxHashSet::default();
+                while let Some(event) = replay.next() {
+                    match event {
+                        Event::Activated(cell) => assert!(active.insert(cell)),
+                        Event::Deactivated(cell) => assert!(active.remove(&cell)),
+                        _ => {}
+                    }
+                    assert_eq!(replay.frontier().collect::<FxHashSet<_>>(), active);
+                }
+
+                assert!(active.is_empty(), "{} left cells active", generator.name());
+                assert_eq!(links(replay.grid()), links(&batch), "{}", generator.name());
+            }
+        }
+    }
+}
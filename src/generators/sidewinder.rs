@@ -1,123 +1,162 @@
-use rand::seq::SliceRandom;
+use rand::{seq::SliceRandom, Rng, RngCore};
 
 use crate::{
+    generators::{Event, Generator},
     grid::Grid,
     kind::{Hex, Regular, ThreeD},
 };
 
-impl Grid<Regular> {
-    pub fn sidewinder(mut self) -> Self {
-        for row in self.rows() {
+pub struct Sidewinder;
+
+impl Generator<Regular> for Sidewinder {
+    fn name(&self) -> &'static str {
+        "sidewinder"
+    }
+
+    fn generate(&self, grid: &mut Grid<Regular>, rng: &mut dyn RngCore) {
+        for row in grid.rows() {
             let mut run = Vec::new();
 
             for cell in row {
                 run.push(cell);
+                grid.record(|| Event::Activated(cell));
 
-                let at_east_boundary = self.east(cell).is_none();
-                let at_north_boundary = self.north(cell).is_none();
+                let at_east_boundary = grid.east(cell).is_none();
+                let at_north_boundary = grid.north(cell).is_none();
 
-                let should_close = at_east_boundary || (!at_north_boundary && rand::random());
+                let should_close = at_east_boundary || (!at_north_boundary && rng.gen());
 
                 if should_close {
-                    let member = run
-                        .choose(&mut rand::thread_rng())
-                        .copied()
-                        .expect("run should be non-empty");
+                    let member = run.choose(rng).copied().expect("run should be non-empty");
 
-                    if let Some(north) = self.north(member) {
-                        self.link(member, north);
+                    if let Some(north) = grid.north(member) {
+                        grid.link(member, north);
+                    }
+                    for cell in run.drain(..) {
+                        grid.record(|| Event::Deactivated(cell));
                     }
-                    run.clear();
                 } else {
-                    let east = self
+                    let east = grid
                         .east(cell)
                         .expect("east should always exist when not closing a run");
-                    self.link(cell, east);
+                    grid.link(cell, east);
                 }
             }
         }
-
-        self
     }
 }
 
-impl Grid<Hex> {
-    pub fn sidewinder(mut self) -> Self {
-        for row in self.rows() {
+impl Generator<Hex> for Sidewinder {
+    fn name(&self) -> &'static str {
+        "sidewinder"
+    }
+
+    fn generate(&self, grid: &mut Grid<Hex>, rng: &mut dyn RngCore) {
+        for row in grid.rows() {
             let mut run = Vec::new();
 
             for cell in row {
                 run.push(cell);
+                grid.record(|| Event::Activated(cell));
 
-                let at_east_boundary = self.get_next_in_row(cell).is_none();
-                let at_north_boundary = self.north(cell).is_none();
+                let at_east_boundary = grid.get_next_in_row(cell).is_none();
+                let at_north_boundary = grid.north(cell).is_none();
 
-                let should_close = at_east_boundary || (!at_north_boundary && rand::random());
+                let should_close = at_east_boundary || (!at_north_boundary && rng.gen());
 
                 if should_close {
-                    let member = run
-                        .choose(&mut rand::thread_rng())
-                        .copied()
-                        .expect("run should be non-empty");
+                    let member = run.choose(rng).copied().expect("run should be non-empty");
 
-                    if let Some(north) = self.north(member) {
-                        self.link(member, north);
+                    if let Some(north) = grid.north(member) {
+                        grid.link(member, north);
+                    }
+                    for cell in run.drain(..) {
+                        grid.record(|| Event::Deactivated(cell));
                     }
-                    run.clear();
                 } else {
-                    let east = self
+                    let east = grid
                         .get_next_in_row(cell)
                         .expect("east should always exist when not closing a run");
-                    self.link(cell, east);
+                    grid.link(cell, east);
                 }
             }
         }
-
-        self
     }
 }
 
-impl Grid<ThreeD> {
-    pub fn sidewinder(mut self) -> Self {
-        for row in self.rows() {
+impl Generator<ThreeD> for Sidewinder {
+    fn name(&self) -> &'static str {
+        "sidewinder"
+    }
+
+    fn generate(&self, grid: &mut Grid<ThreeD>, rng: &mut dyn RngCore) {
+        for row in grid.rows() {
             let mut run = Vec::new();
 
             for cell in row {
                 run.push(cell);
+                grid.record(|| Event::Activated(cell));
 
-                let at_east_boundary = self.east(cell).is_none();
-                let at_north_boundary = self.north(cell).is_none();
+                let at_east_boundary = grid.east(cell).is_none();
+                let at_north_boundary = grid.north(cell).is_none();
 
-                let should_close = at_east_boundary || (!at_north_boundary && rand::random());
+                let should_close = at_east_boundary || (!at_north_boundary && rng.gen());
 
                 if should_close {
-                    let member = run
-                        .choose(&mut rand::thread_rng())
-                        .copied()
-                        .expect("run should be non-empty");
+                    let member = run.choose(rng).copied().expect("run should be non-empty");
 
-                    match (self.north(member), self.up(member)) {
+                    match (grid.north(member), grid.up(member)) {
                         (Some(north), Some(up)) => {
-                            if rand::random() {
-                                self.link(member, north);
+                            if rng.gen() {
+                                grid.link(member, north);
                             } else {
-                                self.link(member, up);
+                                grid.link(member, up);
                             }
                         }
-                        (None, Some(other)) | (Some(other), None) => self.link(member, other),
+                        (None, Some(other)) | (Some(other), None) => grid.link(member, other),
                         (None, None) => {}
                     }
 
-                    run.clear();
+                    for cell in run.drain(..) {
+                        grid.record(|| Event::Deactivated(cell));
+                    }
                 } else {
-                    let east = self
+                    let east = grid
                         .east(cell)
                         .expect("east should always exist when not closing a run");
-                    self.link(cell, east);
+                    grid.link(cell, east);
                 }
             }
         }
+    }
+}
+
+impl Grid<Regular> {
+    pub fn sidewinder(self) -> Self {
+        self.sidewinder_with_rng(&mut rand::thread_rng())
+    }
+
+    pub fn sidewinder_with_rng(self, rng: &mut impl Rng) -> Self {
+        self.generate(&Sidewinder, rng)
+    }
+}
+
+impl Grid<Hex> {
+    pub fn sidewinder(self) -> Self {
+        self.sidewinder_with_rng(&mut rand::thread_rng())
+    }
+
+    pub fn sidewinder_with_rng(self, rng: &mut impl Rng) -> Self {
+        self.generate(&Sidewinder, rng)
+    }
+}
+
+impl Grid<ThreeD> {
+    pub fn sidewinder(self) -> Self {
+        self.sidewinder_with_rng(&mut rand::thread_rng())
+    }
 
-        self
+    pub fn sidewinder_with_rng(self, rng: &mut impl Rng) -> Self {
+        self.generate(&Sidewinder, rng)
     }
 }
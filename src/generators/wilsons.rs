@@ -1,37 +1,69 @@
 use rand::{
     seq::{IteratorRandom, SliceRandom},
-    Rng,
+    Rng, RngCore,
 };
 
-use crate::{grid::Grid, kind::Kind};
+use crate::{
+    generators::{Event, Generator},
+    grid::Grid,
+    kind::Kind,
+};
 
-impl<K: Kind> Grid<K> {
-    pub fn wilsons(mut self) -> Self {
-        let mut unvisited = self.cells();
+pub struct Wilsons;
+
+impl<K: Kind> Generator<K> for Wilsons {
+    fn name(&self) -> &'static str {
+        "wilsons"
+    }
 
-        let first_idx = rand::thread_rng().gen_range(0..unvisited.len());
-        unvisited.swap_remove(first_idx);
+    fn generate(&self, grid: &mut Grid<K>, rng: &mut dyn RngCore) {
+        let mut unvisited = grid.cells();
 
-        while let Some(mut cell) = unvisited.choose(&mut rand::thread_rng()).copied() {
+        let first_idx = rng.gen_range(0..unvisited.len());
+        let first = unvisited.swap_remove(first_idx);
+        grid.record(|| Event::Visited(first));
+
+        while let Some(mut cell) = unvisited.choose(rng).copied() {
             let mut path = vec![cell];
+            grid.record(|| Event::Visited(cell));
+            grid.record(|| Event::Activated(cell));
 
-            while unvisited.contains(&cell) {
-                cell = self
+            loop {
+                let next = grid
                     .neighbours(cell)
-                    .choose(&mut rand::thread_rng())
+                    .choose(rng)
                     .expect("neighbours should be non-empty");
+                let reaches_maze = !unvisited.contains(&next);
+                // the walk can't end in a cell its own path tunnels under, as the passage into it
+                // would leave the crossing without a straight passage over it
+                if reaches_maze
+                    && path
+                        .windows(2)
+                        .any(|pair| K::crossing(grid, pair[0], pair[1]) == Some(next))
+                {
+                    continue;
+                }
+                cell = next;
                 let position = path.iter().position(|c| *c == cell);
 
                 match position {
                     Some(pos) => {
-                        path.drain(pos + 1..);
+                        for erased in path.drain(pos + 1..) {
+                            grid.record(|| Event::Deactivated(erased));
+                        }
+                    }
+                    None => {
+                        grid.record(|| Event::Activated(cell));
+                        path.push(cell);
                     }
-                    None => path.push(cell),
+                }
+                if reaches_maze {
+                    break;
                 }
             }
 
             for idx in 0..=path.len() - 2 {
-                self.link(path[idx], path[idx + 1]);
+                grid.link(path[idx], path[idx + 1]);
                 unvisited.swap_remove(
                     unvisited
                         .iter()
@@ -39,8 +71,19 @@ impl<K: Kind> Grid<K> {
                         .expect("cell should be in the unvisited list"),
                 );
             }
+            for cell in path {
+                grid.record(|| Event::Deactivated(cell));
+            }
         }
+    }
+}
+
+impl<K: Kind> Grid<K> {
+    pub fn wilsons(self) -> Self {
+        self.wilsons_with_rng(&mut rand::thread_rng())
+    }
 
-        self
+    pub fn wilsons_with_rng(self, rng: &mut impl Rng) -> Self {
+        self.generate(&Wilsons, rng)
     }
 }
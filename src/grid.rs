@@ -1,17 +1,17 @@
 use core::fmt;
-use std::{cmp::Ordering, collections::BinaryHeap, f32};
-
-use image::{ImageBuffer, Rgb, RgbImage};
-use imageproc::{
-    drawing::{
-        draw_antialiased_line_segment_mut, draw_filled_rect_mut, draw_hollow_circle_mut,
-        draw_polygon_mut,
-    },
-    pixelops,
-    point::Point,
-    rect::Rect,
+use std::{
+    cmp::Ordering,
+    collections::BinaryHeap,
+    f32,
+    fs::{self, File},
+    io::{BufWriter, Write},
+    path::Path,
 };
-use petgraph::prelude::UnGraphMap;
+
+use either::Either;
+use image::{codecs::png::PngEncoder, ColorType, ImageEncoder, Rgb, RgbImage};
+use imageproc::{point::Point, rect::Rect};
+use petgraph::{prelude::UnGraphMap, visit::NodeIndexable};
 use rand::{
     seq::{IteratorRandom, SliceRandom},
     Rng,
@@ -19,34 +19,50 @@ use rand::{
 use rustc_hash::FxHashMap;
 
 use crate::{
+    canvas::{Canvas, Raster, Svg},
     cell::{
-        CellKind, HexCell, OverCell, PolarCell, RegularCell, ThreeDCell, TriangleCell, UnderCell,
-        WeaveCell, WeightedCell,
+        CellKind, CubeCell, HexCell, OverCell, PolarCell, RegularCell, ThreeDCell, TriangleCell,
+        UnderCell, UpsilonCell, WeaveCell, WeightedCell,
     },
     distances::Distances,
-    kind::{Hex, Kind, Masked, Polar, Regular, ThreeD, Triangle, Weave, Weighted},
+    error::MazeError,
+    generators::Event,
+    index::CellIndex,
+    kind::{
+        Cube, Hex, Join, Kind, Masked, Polar, Rectangular, Regular, ThreeD, Triangle, Upsilon,
+        Weave, Weighted, Wrapping,
+    },
+    links::{Links, PackedLinks},
+    path::Path as MazePath,
+    style::RenderOptions,
 };
 
-const BACKGROUND: Rgb<u8> = Rgb([255, 255, 255]);
-const WALL: Rgb<u8> = Rgb([0, 0, 0]);
 const RED: Rgb<u8> = Rgb([255, 0, 0]);
+const WEAVE_INSET: f32 = 0.1;
 
+#[derive(Clone)]
 pub struct Grid<K: Kind> {
     kind: K,
-    links: UnGraphMap<K::Cell, ()>,
+    links: Links<K>,
+    /// Cells by position, which packed grids leave empty as their kind makes them instead.
+    index: CellIndex<K::Cell>,
     start: Option<K::Cell>,
     goal: Option<K::Cell>,
+    events: Option<Vec<Event<K::Cell>>>,
 }
 
 impl<K: Kind> Grid<K> {
     pub fn new(kind: K, start: Option<K::Cell>, goal: Option<K::Cell>) -> Self {
         let links = kind.prepare_grid();
+        let index = CellIndex::new(links.nodes());
 
         Self {
             kind,
-            links,
+            links: Links::Graph(links),
+            index,
             start,
             goal,
+            events: None,
         }
     }
 
@@ -57,8 +73,29 @@ impl<K: Kind> Grid<K> {
         self.goal = Some(start);
     }
 
+    pub fn start(&self) -> Option<K::Cell> {
+        self.start
+    }
+    pub fn goal(&self) -> Option<K::Cell> {
+        self.goal
+    }
+
+    /// The cell at a position, whichever coordinates the kind uses.
+    pub(crate) fn cell_at(&self, row: isize, col: isize, level: isize) -> Option<K::Cell> {
+        match &self.links {
+            Links::Graph(_) => self.index.get(row, col, level),
+            Links::Packed(links) => links.cell_at(&self.kind, row, col, level),
+        }
+    }
+
     pub fn cells(&self) -> Vec<K::Cell> {
-        self.links.nodes().collect()
+        self.nodes().collect()
+    }
+    fn nodes(&self) -> impl Iterator<Item = K::Cell> + '_ {
+        match &self.links {
+            Links::Graph(links) => Either::Left(links.nodes()),
+            Links::Packed(links) => Either::Right(links.cells(&self.kind)),
+        }
     }
     pub fn neighbouring_cells(&self) -> Vec<(K::Cell, K::Cell)> {
         K::neighbouring_cells(self)
@@ -68,32 +105,91 @@ impl<K: Kind> Grid<K> {
         K::link(self, cell, other);
     }
     pub fn connect(&mut self, cell: K::Cell, other: K::Cell) {
-        self.links.add_edge(cell, other, ());
+        self.record(|| Event::Linked(cell, other));
+        match &mut self.links {
+            Links::Graph(links) => {
+                links.add_edge(cell, other, ());
+            }
+            Links::Packed(links) => links.insert(cell, other),
+        }
     }
     pub fn unlink(&mut self, cell: K::Cell, other: K::Cell) {
-        self.links.remove_edge(cell, other);
+        self.record(|| Event::Unlinked(cell, other));
+        match &mut self.links {
+            Links::Graph(links) => {
+                links.remove_edge(cell, other);
+            }
+            Links::Packed(links) => links.remove(cell, other),
+        }
     }
 
     pub fn links(&self, cell: K::Cell) -> impl Iterator<Item = K::Cell> + '_ {
-        self.links.neighbors(cell)
+        match &self.links {
+            Links::Graph(links) => Either::Left(links.neighbors(cell)),
+            Links::Packed(links) => Either::Right(
+                links
+                    .neighbours(cell)
+                    .filter_map(|(row, col, level)| links.cell_at(&self.kind, row, col, level)),
+            ),
+        }
     }
     pub fn are_linked(&self, cell: K::Cell, other: K::Cell) -> bool {
-        self.links.contains_edge(cell, other)
+        match &self.links {
+            Links::Graph(links) => links.contains_edge(cell, other),
+            Links::Packed(links) => links.contains(cell, other),
+        }
     }
 
     pub fn neighbours(&self, cell: K::Cell) -> impl Iterator<Item = K::Cell> + '_ {
         self.kind.neighbours(self, cell)
     }
 
+    /// Starts collecting the events generators emit while carving this grid.
+    pub(crate) fn start_recording(&mut self) {
+        self.events = Some(Vec::new());
+    }
+    pub(crate) fn take_events(&mut self) -> Vec<Event<K::Cell>> {
+        self.events.take().unwrap_or_default()
+    }
+    /// Records an event if recording, only building it when it will be kept.
+    pub(crate) fn record(&mut self, event: impl FnOnce() -> Event<K::Cell>) {
+        if let Some(events) = &mut self.events {
+            events.push(event());
+        }
+    }
+
     pub fn get_random_cell(&self) -> K::Cell {
-        self.links
-            .nodes()
-            .choose(&mut rand::thread_rng())
+        self.get_random_cell_with_rng(&mut rand::thread_rng())
+    }
+
+    pub fn get_random_cell_with_rng(&self, rng: &mut (impl Rng + ?Sized)) -> K::Cell {
+        self.try_get_random_cell_with_rng(rng)
             .expect("at least one cell in the grid")
     }
 
+    /// Like [`Grid::get_random_cell`], but failing on a grid with no cells, such as one made
+    /// from a mask that hides everything.
+    pub fn try_get_random_cell(&self) -> Result<K::Cell, MazeError> {
+        self.try_get_random_cell_with_rng(&mut rand::thread_rng())
+    }
+    pub fn try_get_random_cell_with_rng(
+        &self,
+        rng: &mut (impl Rng + ?Sized),
+    ) -> Result<K::Cell, MazeError> {
+        // an index drawn the same way for both storages keeps them carving the same mazes
+        let index = (0..self.size()).choose(rng).ok_or(MazeError::EmptyGrid)?;
+
+        Ok(match &self.links {
+            Links::Graph(links) => links.from_index(index),
+            Links::Packed(links) => links.nth(&self.kind, index).expect("index within the grid"),
+        })
+    }
+
     pub fn size(&self) -> usize {
-        self.links.node_count()
+        match &self.links {
+            Links::Graph(links) => links.node_count(),
+            Links::Packed(links) => links.len(),
+        }
     }
 
     pub fn dead_ends(&self) -> Vec<K::Cell> {
@@ -103,12 +199,19 @@ impl<K: Kind> Grid<K> {
             .collect()
     }
 
-    pub fn braid(mut self, p: f32) -> Self {
+    /// Removes each dead end with probability `p` by opening it onto a neighbour, preferring
+    /// one that is also a dead end. Dead ends with no unlinked neighbour, such as the tips of a
+    /// grid one cell wide, are left as they are, so even `p` of one can leave some behind.
+    pub fn braid(self, p: f32) -> Self {
+        self.braid_with_rng(p, &mut rand::thread_rng())
+    }
+
+    pub fn braid_with_rng(mut self, p: f32, rng: &mut impl Rng) -> Self {
         let mut dead_ends = self.dead_ends();
-        dead_ends.shuffle(&mut rand::thread_rng());
+        dead_ends.shuffle(rng);
 
         for cell in dead_ends {
-            if self.links(cell).count() != 1 || rand::thread_rng().gen_range(0.0..=1.0) > p {
+            if self.links(cell).count() != 1 || rng.gen_range(0.0..=1.0) > p {
                 continue;
             }
 
@@ -121,14 +224,13 @@ impl<K: Kind> Grid<K> {
                 .filter(|n| self.links(**n).count() == 1)
                 .copied();
 
+            // a dead end at the tip of a one cell wide grid has nothing else to open onto
             let neighbour = dead_end_neighbours
-                .choose(&mut rand::thread_rng())
-                .unwrap_or_else(|| {
-                    *neighbours
-                        .choose(&mut rand::thread_rng())
-                        .expect("neighbours should be non-empty")
-                });
-            self.link(cell, neighbour);
+                .choose(rng)
+                .or_else(|| neighbours.choose(rng).copied());
+            if let Some(neighbour) = neighbour {
+                self.link(cell, neighbour);
+            }
         }
 
         self
@@ -177,24 +279,130 @@ impl<K: Kind> Grid<K> {
         weights
     }
 
+    /// The route from the start to the goal, if both are set and the goal can be reached.
+    pub fn path(&self) -> Option<MazePath<K>> {
+        let (start, goal) = (self.start?, self.goal?);
+        self.distances_from(start)
+            .path_to(goal, self)
+            .map(MazePath::from)
+    }
+
     pub fn distances(&self) -> Option<Distances<K>> {
         match (self.start, self.goal) {
             (None, None) => None,
             (None, Some(cell)) | (Some(cell), None) => Some(self.distances_from(cell)),
-            (Some(start), Some(goal)) => Some(self.distances_from(start).path_to(goal, self)),
+            (Some(start), Some(goal)) => {
+                let distances = self.distances_from(start);
+                Some(distances.path_to(goal, self).unwrap_or(distances))
+            }
+        }
+    }
+
+    /// Distances to shade by when rendering with `options`.
+    fn shading(&self, options: &RenderOptions) -> Option<Shading<K>> {
+        options
+            .shade
+            .then(|| self.distances())
+            .flatten()
+            .map(Shading::Distances)
+    }
+
+    fn background_mode(
+        &self,
+        shading: Option<Shading<K>>,
+        options: &RenderOptions,
+    ) -> Option<SavePngMode<K>> {
+        let shading = shading.filter(|_| options.shade)?;
+        let colors = match shading {
+            Shading::Distances(distances) => {
+                let (_, max) = distances.max();
+                self.cells()
+                    .into_iter()
+                    .filter_map(|cell| {
+                        if cell.weight() > 1 {
+                            return Some((cell, options.weighted));
+                        }
+
+                        let distance = distances.get(&cell)?;
+                        let position = if max == 0 {
+                            0.0
+                        } else {
+                            distance as f32 / max as f32
+                        };
+                        Some((cell, options.gradient.color_at(position)))
+                    })
+                    .collect()
+            }
+            Shading::Highlight(colors) => colors,
+        };
+
+        Some(SavePngMode::Background(colors))
+    }
+
+    /// Draws the solution line and start/goal markers asked for by `options`, with `center`
+    /// giving where each cell is drawn. Levels are drawn side by side, so wherever the route
+    /// changes level a thin connector carries the line over to the other level.
+    fn draw_solution(
+        &self,
+        canvas: &mut impl Canvas,
+        options: &RenderOptions,
+        marker_radius: i32,
+        center: impl Fn(K::Cell) -> (i32, i32),
+    ) {
+        if let (Some(color), Some(path)) = (options.solution, self.path()) {
+            let mut previous = None;
+            for run in path
+                .cells()
+                .chunk_by(|cell, next| cell.level() == next.level())
+            {
+                let points = run.iter().map(|cell| center(*cell)).collect::<Vec<_>>();
+                if let Some(previous) = previous {
+                    canvas.line(previous, points[0], color);
+                }
+                canvas.polyline(&points, color, options.solution_thickness);
+                previous = points.last().copied();
+            }
+        }
+
+        self.draw_markers(canvas, options, marker_radius, center);
+    }
+
+    /// Like [`Grid::draw_solution`], but breaking the line between whichever cells `is_apart`
+    /// says aren't drawn next to each other.
+    fn draw_solution_with_breaks(
+        &self,
+        canvas: &mut impl Canvas,
+        options: &RenderOptions,
+        marker_radius: i32,
+        center: impl Fn(K::Cell) -> (i32, i32),
+        is_apart: impl Fn(K::Cell, K::Cell) -> bool,
+    ) {
+        if let (Some(color), Some(path)) = (options.solution, self.path()) {
+            for run in path.cells().chunk_by(|cell, next| !is_apart(*cell, *next)) {
+                let points = run.iter().map(|cell| center(*cell)).collect::<Vec<_>>();
+                canvas.polyline(&points, color, options.solution_thickness);
+            }
         }
+
+        self.draw_markers(canvas, options, marker_radius, center);
     }
 
-    fn background_for_cell(distances: &Distances<K>, cell: K::Cell) -> Option<Rgb<u8>> {
-        if cell.weight() > 1 {
-            Some(RED)
-        } else {
-            let distance = distances.get(&cell)?;
-            let (_, max) = distances.max();
-            let intensity = (max - distance) as f32 / max as f32;
-            let dark = (255.0 * intensity).round() as u8;
-            let bright = 128 + (127.0 * intensity) as u8;
-            Some(Rgb([dark, bright, dark]))
+    fn draw_markers(
+        &self,
+        canvas: &mut impl Canvas,
+        options: &RenderOptions,
+        marker_radius: i32,
+        center: impl Fn(K::Cell) -> (i32, i32),
+    ) {
+        if options.markers {
+            for (cell, color) in [
+                (self.start, options.start_marker),
+                (self.goal, options.goal_marker),
+            ] {
+                if let Some(cell) = cell {
+                    canvas.circle(center(cell), marker_radius, color);
+                }
+            }
         }
     }
 
@@ -202,11 +410,31 @@ impl<K: Kind> Grid<K> {
         self.kind.num_rows()
     }
 
+    pub fn get_kind(&self) -> &K {
+        &self.kind
+    }
+
     pub fn get_kind_mut(&mut self) -> &mut K {
         &mut self.kind
     }
 }
 
+impl<K: Rectangular> Grid<K> {
+    /// Creates a grid that stores its passages as packed wall bits and makes its cells from
+    /// their position rather than keeping both in a graph, which takes far less memory for large
+    /// mazes. Generators carve the same maze from the same seed on either.
+    pub fn new_packed(kind: K, start: Option<K::Cell>, goal: Option<K::Cell>) -> Self {
+        Self {
+            links: Links::Packed(PackedLinks::new(&kind)),
+            kind,
+            index: CellIndex::new(std::iter::empty()),
+            start,
+            goal,
+            events: None,
+        }
+    }
+}
+
 impl Grid<Regular> {
     pub fn num_cols(&self) -> usize {
         self.kind.cols
@@ -233,7 +461,7 @@ macro_rules! save_png_inset_helpers {
     ($T:ty) => {
         fn render_cell_with_inset(
             &self,
-            img: &mut ImageBuffer<Rgb<u8>, Vec<u8>>,
+            canvas: &mut impl Canvas,
             cell: <$T as Kind>::Cell,
             mode: &SavePngMode<$T>,
             cell_size: u32,
@@ -244,58 +472,38 @@ macro_rules! save_png_inset_helpers {
                 Self::cell_coordinates_with_inset(xy, cell_size, inset);
 
             match mode {
-                SavePngMode::Background(distances) => {
-                    if let Some(color) = Self::background_for_cell(distances, cell) {
+                SavePngMode::Background(colors) => {
+                    if let Some(color) = colors.get(&cell).copied() {
                         let inner_size = cell_size - 2 * inset;
 
-                        draw_filled_rect_mut(
-                            img,
-                            Rect::at(x2, y2).of_size(inner_size, inner_size),
-                            color,
-                        );
+                        canvas.rect(Rect::at(x2, y2).of_size(inner_size, inner_size), color);
 
                         if self
                             .north(cell)
                             .is_some_and(|north| self.are_linked(cell, north))
                         {
-                            draw_filled_rect_mut(
-                                img,
-                                Rect::at(x2, y1).of_size(inner_size, inset),
-                                color,
-                            );
+                            canvas.rect(Rect::at(x2, y1).of_size(inner_size, inset), color);
                         }
 
                         if self
                             .south(cell)
                             .is_some_and(|south| self.are_linked(cell, south))
                         {
-                            draw_filled_rect_mut(
-                                img,
-                                Rect::at(x2, y3).of_size(inner_size, inset),
-                                color,
-                            );
+                            canvas.rect(Rect::at(x2, y3).of_size(inner_size, inset), color);
                         }
 
                         if self
                             .west(cell)
                             .is_some_and(|west| self.are_linked(cell, west))
                         {
-                            draw_filled_rect_mut(
-                                img,
-                                Rect::at(x1, y2).of_size(inset, inner_size),
-                                color,
-                            );
+                            canvas.rect(Rect::at(x1, y2).of_size(inset, inner_size), color);
                         }
 
                         if self
                             .east(cell)
                             .is_some_and(|east| self.are_linked(cell, east))
                         {
-                            draw_filled_rect_mut(
-                                img,
-                                Rect::at(x3, y2).of_size(inset, inner_size),
-                                color,
-                            );
+                            canvas.rect(Rect::at(x3, y2).of_size(inset, inner_size), color);
                         }
                     }
                 }
@@ -304,112 +512,40 @@ macro_rules! save_png_inset_helpers {
                         .north(cell)
                         .is_some_and(|north| self.are_linked(cell, north))
                     {
-                        draw_antialiased_line_segment_mut(
-                            img,
-                            (x2, y1),
-                            (x2, y2),
-                            WALL,
-                            pixelops::interpolate,
-                        );
-                        draw_antialiased_line_segment_mut(
-                            img,
-                            (x3, y1),
-                            (x3, y2),
-                            WALL,
-                            pixelops::interpolate,
-                        );
+                        canvas.wall((x2, y1), (x2, y2));
+                        canvas.wall((x3, y1), (x3, y2));
                     } else {
-                        draw_antialiased_line_segment_mut(
-                            img,
-                            (x2, y2),
-                            (x3, y2),
-                            WALL,
-                            pixelops::interpolate,
-                        );
+                        canvas.wall((x2, y2), (x3, y2));
                     }
 
                     if self
                         .south(cell)
                         .is_some_and(|south| self.are_linked(cell, south))
                     {
-                        draw_antialiased_line_segment_mut(
-                            img,
-                            (x2, y3),
-                            (x2, y4),
-                            WALL,
-                            pixelops::interpolate,
-                        );
-                        draw_antialiased_line_segment_mut(
-                            img,
-                            (x3, y3),
-                            (x3, y4),
-                            WALL,
-                            pixelops::interpolate,
-                        );
+                        canvas.wall((x2, y3), (x2, y4));
+                        canvas.wall((x3, y3), (x3, y4));
                     } else {
-                        draw_antialiased_line_segment_mut(
-                            img,
-                            (x2, y3),
-                            (x3, y3),
-                            WALL,
-                            pixelops::interpolate,
-                        );
+                        canvas.wall((x2, y3), (x3, y3));
                     }
 
                     if self
                         .west(cell)
                         .is_some_and(|west| self.are_linked(cell, west))
                     {
-                        draw_antialiased_line_segment_mut(
-                            img,
-                            (x1, y2),
-                            (x2, y2),
-                            WALL,
-                            pixelops::interpolate,
-                        );
-                        draw_antialiased_line_segment_mut(
-                            img,
-                            (x1, y3),
-                            (x2, y3),
-                            WALL,
-                            pixelops::interpolate,
-                        );
+                        canvas.wall((x1, y2), (x2, y2));
+                        canvas.wall((x1, y3), (x2, y3));
                     } else {
-                        draw_antialiased_line_segment_mut(
-                            img,
-                            (x2, y2),
-                            (x2, y3),
-                            WALL,
-                            pixelops::interpolate,
-                        );
+                        canvas.wall((x2, y2), (x2, y3));
                     }
 
                     if self
                         .east(cell)
                         .is_some_and(|east| self.are_linked(cell, east))
                     {
-                        draw_antialiased_line_segment_mut(
-                            img,
-                            (x3, y2),
-                            (x4, y2),
-                            WALL,
-                            pixelops::interpolate,
-                        );
-                        draw_antialiased_line_segment_mut(
-                            img,
-                            (x3, y3),
-                            (x4, y3),
-                            WALL,
-                            pixelops::interpolate,
-                        );
+                        canvas.wall((x3, y2), (x4, y2));
+                        canvas.wall((x3, y3), (x4, y3));
                     } else {
-                        draw_antialiased_line_segment_mut(
-                            img,
-                            (x3, y2),
-                            (x3, y3),
-                            WALL,
-                            pixelops::interpolate,
-                        );
+                        canvas.wall((x3, y2), (x3, y3));
                     }
                 }
             }
@@ -433,7 +569,7 @@ macro_rules! save_png_inset_helpers {
 
         fn render_cell_without_inset(
             &self,
-            img: &mut ImageBuffer<Rgb<u8>, Vec<u8>>,
+            canvas: &mut impl Canvas,
             cell: <$T as Kind>::Cell,
             mode: &SavePngMode<$T>,
             cell_size: u32,
@@ -444,58 +580,37 @@ macro_rules! save_png_inset_helpers {
             let y2 = y1 + cell_size as i32;
 
             match mode {
-                SavePngMode::Background(distances) => {
-                    if let Some(color) = Self::background_for_cell(distances, cell) {
-                        draw_filled_rect_mut(
-                            img,
-                            Rect::at(x1, y1).of_size(cell_size, cell_size),
-                            color,
-                        );
+                SavePngMode::Background(colors) => {
+                    if let Some(color) = colors.get(&cell).copied() {
+                        canvas.rect(Rect::at(x1, y1).of_size(cell_size, cell_size), color);
                     }
                 }
                 SavePngMode::Walls => {
-                    if self.north(cell).is_none() {
-                        draw_antialiased_line_segment_mut(
-                            img,
-                            (x1, y1),
-                            (x2, y1),
-                            WALL,
-                            pixelops::interpolate,
-                        );
+                    // the cells above and to the left draw the walls they share with this one,
+                    // unless they're around the edge of a wrapping grid
+                    if self.north(cell).is_none_or(|north| {
+                        north.row != cell.row - 1 && !self.are_linked(cell, north)
+                    }) {
+                        canvas.wall((x1, y1), (x2, y1));
                     }
-                    if self.west(cell).is_none() {
-                        draw_antialiased_line_segment_mut(
-                            img,
-                            (x1, y1),
-                            (x1, y2),
-                            WALL,
-                            pixelops::interpolate,
-                        );
+                    if self
+                        .west(cell)
+                        .is_none_or(|west| west.col != cell.col - 1 && !self.are_linked(cell, west))
+                    {
+                        canvas.wall((x1, y1), (x1, y2));
                     }
 
                     if !self
                         .east(cell)
                         .is_some_and(|east| self.are_linked(cell, east))
                     {
-                        draw_antialiased_line_segment_mut(
-                            img,
-                            (x2, y1),
-                            (x2, y2),
-                            WALL,
-                            pixelops::interpolate,
-                        );
+                        canvas.wall((x2, y1), (x2, y2));
                     }
                     if !self
                         .south(cell)
                         .is_some_and(|south| self.are_linked(cell, south))
                     {
-                        draw_antialiased_line_segment_mut(
-                            img,
-                            (x1, y2),
-                            (x2, y2),
-                            WALL,
-                            pixelops::interpolate,
-                        );
+                        canvas.wall((x1, y2), (x2, y2));
                     }
                 }
             }
@@ -503,6 +618,40 @@ macro_rules! save_png_inset_helpers {
     };
 }
 
+/// Implements [`Render`] through the grid's own `draw`, which every kind has with the same
+/// signature.
+macro_rules! impl_render {
+    (@impl [$($generics:tt)*] $T:ty) => {
+        impl<$($generics)*> Render for $T {
+            fn render(
+                grid: &Grid<Self>,
+                shading: Option<Shading<Self>>,
+                cell_size: u32,
+                inset: f32,
+                options: &RenderOptions,
+            ) -> RgbImage {
+                grid.draw::<Raster>(shading, cell_size, inset, options)
+            }
+
+            fn render_svg(
+                grid: &Grid<Self>,
+                shading: Option<Shading<Self>>,
+                cell_size: u32,
+                inset: f32,
+                options: &RenderOptions,
+            ) -> String {
+                grid.draw::<Svg>(shading, cell_size, inset, options)
+            }
+        }
+    };
+    (impl<$K:ident: $bound:path> $T:ty) => {
+        impl_render!(@impl [$K: $bound] $T);
+    };
+    ($($T:ty),+ $(,)?) => {
+        $(impl_render!(@impl [] $T);)+
+    };
+}
+
 macro_rules! impl_rectangular {
     ($($T:ty),+ $(,)?) => {
         $(
@@ -521,18 +670,35 @@ macro_rules! impl_rectangular {
                 }
 
                 pub fn get(&self, row: isize, col: isize) -> Option<<$T as Kind>::Cell> {
-                    self.links.nodes().find(|n| n.row == row && n.col == col)
+                    self.cell_at(row, col, 0)
                 }
 
                 pub fn save_png(&self, file_name: &str, cell_size: u32, inset: f32) {
+                    let options = RenderOptions::default();
+                    self.draw::<Raster>(self.shading(&options), cell_size, inset, &options)
+                        .save(format!("images/{file_name}.png"))
+                        .expect("image to be saved");
+                }
+
+                pub fn save_svg(&self, file_name: &str, cell_size: u32, inset: f32) {
+                    fs::write(format!("images/{file_name}.svg"), self.to_svg(cell_size, inset))
+                        .expect("svg to be saved");
+                }
+
+                pub fn to_svg(&self, cell_size: u32, inset: f32) -> String {
+                    let options = RenderOptions::default();
+                    self.draw::<Svg>(self.shading(&options), cell_size, inset, &options)
+                }
+
+                fn draw<C: Canvas>(&self, shading: Option<Shading<$T>>, cell_size: u32, inset: f32, options: &RenderOptions) -> C::Output {
                     let img_width = cell_size * self.num_cols() as u32;
                     let img_height = cell_size * self.num_rows() as u32;
                     let inset = (cell_size as f32 * inset) as u32;
 
-                    let mut img = RgbImage::from_pixel(img_width + 1, img_height + 1, BACKGROUND);
+                    let mut canvas = C::new(img_width + 1, img_height + 1, options);
 
                     let modes = [
-                        self.distances().map(SavePngMode::Background),
+                        self.background_mode(shading, options),
                         Some(SavePngMode::Walls),
                     ]
                     .into_iter()
@@ -543,20 +709,29 @@ macro_rules! impl_rectangular {
                             let y = cell.row as i32 * cell_size as i32;
 
                             if inset > 0 {
-                                self.render_cell_with_inset(&mut img, cell, &mode, cell_size, (x, y), inset);
+                                self.render_cell_with_inset(&mut canvas, cell, &mode, cell_size, (x, y), inset);
                             } else {
-                                self.render_cell_without_inset(&mut img, cell, &mode, cell_size, (x, y));
+                                self.render_cell_without_inset(&mut canvas, cell, &mode, cell_size, (x, y));
                             }
                         }
                     }
 
-                    img.save(format!("images/{file_name}.png"))
-                        .expect("image to be saved");
+                    let half = cell_size as i32 / 2;
+                    self.draw_solution(&mut canvas, options, half / 2, |cell| {
+                        (
+                            cell.col as i32 * cell_size as i32 + half,
+                            cell.row as i32 * cell_size as i32 + half,
+                        )
+                    });
+
+                    canvas.finish()
                 }
 
                 save_png_inset_helpers!($T);
             }
 
+            impl_render!($T);
+
             impl fmt::Display for Grid<$T> {
                 fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
                     let is_space_between_empty = |cell, other| match (cell, other) {
@@ -667,16 +842,20 @@ impl Grid<Polar> {
     pub fn counter_clockwise(&self, cell: PolarCell) -> Option<PolarCell> {
         self.get(cell.row, cell.col - 1)
     }
+    /// The number of cells in `row`, which grows as the rows get further from the centre.
+    pub fn row_len(&self, row: isize) -> usize {
+        self.index.row_len(row)
+    }
     pub fn inward(&self, cell: PolarCell) -> Option<PolarCell> {
         if cell.row == 0 {
             return None;
         }
 
-        let ratio = row_len(&self.links, cell.row) / row_len(&self.links, cell.row - 1);
+        let ratio = self.index.row_len(cell.row) / self.index.row_len(cell.row - 1);
         self.get(cell.row - 1, cell.col / ratio as isize)
     }
     pub fn outward(&self, cell: PolarCell) -> impl Iterator<Item = PolarCell> {
-        let ratio = (row_len(&self.links, cell.row + 1) / row_len(&self.links, cell.row)) as isize;
+        let ratio = (self.index.row_len(cell.row + 1) / self.index.row_len(cell.row)) as isize;
 
         (cell.col * ratio..cell.col * ratio + ratio).map(move |col| PolarCell {
             row: cell.row + 1,
@@ -685,14 +864,38 @@ impl Grid<Polar> {
     }
 
     pub fn get(&self, row: isize, col: isize) -> Option<PolarCell> {
-        let cell = PolarCell {
-            row,
-            col: col % row_len(&self.links, row) as isize,
-        };
-        self.links.contains_node(cell).then_some(cell)
+        let row_len = self.index.row_len(row) as isize;
+        if row_len == 0 {
+            return None;
+        }
+
+        self.cell_at(row, col % row_len, 0)
     }
 
     pub fn save_png(&self, file_name: &str, cell_size: u32) {
+        let options = RenderOptions::default();
+        self.draw::<Raster>(self.shading(&options), cell_size, 0.0, &options)
+            .save(format!("images/{file_name}.png"))
+            .expect("image to be saved");
+    }
+
+    pub fn save_svg(&self, file_name: &str, cell_size: u32) {
+        fs::write(format!("images/{file_name}.svg"), self.to_svg(cell_size))
+            .expect("svg to be saved");
+    }
+
+    pub fn to_svg(&self, cell_size: u32) -> String {
+        let options = RenderOptions::default();
+        self.draw::<Svg>(self.shading(&options), cell_size, 0.0, &options)
+    }
+
+    fn draw<C: Canvas>(
+        &self,
+        shading: Option<Shading<Polar>>,
+        cell_size: u32,
+        _inset: f32,
+        options: &RenderOptions,
+    ) -> C::Output {
         struct CellCoords {
             a: (i32, i32),
             b: (i32, i32),
@@ -702,7 +905,7 @@ impl Grid<Polar> {
 
         let img_size = 2 * self.num_rows() as u32 * cell_size;
 
-        let mut img = RgbImage::from_pixel(img_size + 1, img_size + 1, BACKGROUND);
+        let mut canvas = C::new(img_size + 1, img_size + 1, options);
         let center = img_size as i32 / 2;
 
         let mut coord_map = FxHashMap::default();
@@ -711,7 +914,7 @@ impl Grid<Polar> {
                 continue;
             }
 
-            let theta = 2.0 * f32::consts::PI / row_len(&self.links, cell.row) as f32;
+            let theta = 2.0 * f32::consts::PI / self.index.row_len(cell.row) as f32;
             let inner_radius = cell.row as f32 * cell_size as f32;
             let outer_radius = (cell.row + 1) as f32 * cell_size as f32;
             let theta_ccw = cell.col as f32 * theta;
@@ -737,9 +940,9 @@ impl Grid<Polar> {
             );
         }
 
-        if let Some(distances) = self.distances() {
+        if let Some(SavePngMode::Background(colors)) = self.background_mode(shading, options) {
             for cell in self.cells() {
-                if let Some(color) = Self::background_for_cell(&distances, cell) {
+                if let Some(color) = colors.get(&cell).copied() {
                     if cell.row == 0 {
                         let poly = self
                             .outward(cell)
@@ -754,7 +957,7 @@ impl Grid<Polar> {
                             })
                             .skip(1) // polygon needs to be open
                             .collect::<Vec<_>>();
-                        draw_polygon_mut(&mut img, &poly, color);
+                        canvas.polygon(&poly, color);
                     } else {
                         let CellCoords {
                             a: (ax, ay),
@@ -782,7 +985,7 @@ impl Grid<Polar> {
                                 Point::new(ax, ay),
                             ],
                         };
-                        draw_polygon_mut(&mut img, &poly, color);
+                        canvas.polygon(&poly, color);
                     }
                 }
             }
@@ -804,37 +1007,33 @@ impl Grid<Polar> {
                 .inward(cell)
                 .is_some_and(|inward| self.are_linked(cell, inward))
             {
-                draw_antialiased_line_segment_mut(
-                    &mut img,
-                    (ax, ay),
-                    (cx, cy),
-                    WALL,
-                    pixelops::interpolate,
-                );
+                canvas.wall((ax, ay), (cx, cy));
             }
             if !self
                 .clockwise(cell)
                 .is_some_and(|clockwise| self.are_linked(cell, clockwise))
             {
-                draw_antialiased_line_segment_mut(
-                    &mut img,
-                    (cx, cy),
-                    (dx, dy),
-                    WALL,
-                    pixelops::interpolate,
-                );
+                canvas.wall((cx, cy), (dx, dy));
             }
         }
 
-        draw_hollow_circle_mut(
-            &mut img,
-            (center, center),
-            self.num_rows() as i32 * cell_size as i32,
-            WALL,
-        );
+        canvas.wall_circle((center, center), self.num_rows() as i32 * cell_size as i32);
 
-        img.save(format!("images/{file_name}.png"))
-            .expect("image to be saved");
+        self.draw_solution(&mut canvas, options, cell_size as i32 / 4, |cell| {
+            if cell.row == 0 {
+                return (center, center);
+            }
+
+            let theta = 2.0 * f32::consts::PI / self.index.row_len(cell.row) as f32;
+            let radius = (cell.row as f32 + 0.5) * cell_size as f32;
+            let angle = (cell.col as f32 + 0.5) * theta;
+            (
+                center + (radius * angle.cos()) as i32,
+                center + (radius * angle.sin()) as i32,
+            )
+        });
+
+        canvas.finish()
     }
 }
 
@@ -863,8 +1062,7 @@ impl Grid<Hex> {
     }
 
     pub fn get(&self, row: isize, col: isize) -> Option<HexCell> {
-        let cell = HexCell { row, col };
-        self.links.contains_node(cell).then_some(cell)
+        self.cell_at(row, col, 0)
     }
     pub fn get_next_in_row(&self, cell: HexCell) -> Option<HexCell> {
         self.get(cell.row, cell.col + 1)
@@ -881,6 +1079,29 @@ impl Grid<Hex> {
     }
 
     pub fn save_png(&self, file_name: &str, cell_size: u32) {
+        let options = RenderOptions::default();
+        self.draw::<Raster>(self.shading(&options), cell_size, 0.0, &options)
+            .save(format!("images/{file_name}.png"))
+            .expect("image to be saved");
+    }
+
+    pub fn save_svg(&self, file_name: &str, cell_size: u32) {
+        fs::write(format!("images/{file_name}.svg"), self.to_svg(cell_size))
+            .expect("svg to be saved");
+    }
+
+    pub fn to_svg(&self, cell_size: u32) -> String {
+        let options = RenderOptions::default();
+        self.draw::<Svg>(self.shading(&options), cell_size, 0.0, &options)
+    }
+
+    fn draw<C: Canvas>(
+        &self,
+        shading: Option<Shading<Hex>>,
+        cell_size: u32,
+        _inset: f32,
+        options: &RenderOptions,
+    ) -> C::Output {
         let cell_size = cell_size as f32;
 
         let a_size = cell_size / 2.0;
@@ -890,10 +1111,10 @@ impl Grid<Hex> {
         let img_width = (3.0 * a_size * self.num_cols() as f32 + a_size + 0.5) as u32;
         let img_height = (height * self.num_rows() as f32 + b_size + 0.5) as u32;
 
-        let mut img = RgbImage::from_pixel(img_width + 1, img_height + 1, BACKGROUND);
+        let mut canvas = C::new(img_width + 1, img_height + 1, options);
 
         let modes = [
-            self.distances().map(SavePngMode::Background),
+            self.background_mode(shading, options),
             Some(SavePngMode::Walls),
         ]
         .into_iter()
@@ -919,10 +1140,9 @@ impl Grid<Hex> {
                 let y_s = (cy + b_size) as i32;
 
                 match mode {
-                    SavePngMode::Background(ref distances) => {
-                        if let Some(color) = Self::background_for_cell(distances, cell) {
-                            draw_polygon_mut(
-                                &mut img,
+                    SavePngMode::Background(ref colors) => {
+                        if let Some(color) = colors.get(&cell).copied() {
+                            canvas.polygon(
                                 &[
                                     Point::new(x_fw, y_m),
                                     Point::new(x_nw, y_n),
@@ -937,76 +1157,48 @@ impl Grid<Hex> {
                     }
                     SavePngMode::Walls => {
                         if self.south_west(cell).is_none() {
-                            draw_antialiased_line_segment_mut(
-                                &mut img,
-                                (x_fw, y_m),
-                                (x_nw, y_s),
-                                WALL,
-                                pixelops::interpolate,
-                            );
+                            canvas.wall((x_fw, y_m), (x_nw, y_s));
                         }
                         if self.north_west(cell).is_none() {
-                            draw_antialiased_line_segment_mut(
-                                &mut img,
-                                (x_fw, y_m),
-                                (x_nw, y_n),
-                                WALL,
-                                pixelops::interpolate,
-                            );
+                            canvas.wall((x_fw, y_m), (x_nw, y_n));
                         }
                         if self.north(cell).is_none() {
-                            draw_antialiased_line_segment_mut(
-                                &mut img,
-                                (x_nw, y_n),
-                                (x_ne, y_n),
-                                WALL,
-                                pixelops::interpolate,
-                            );
+                            canvas.wall((x_nw, y_n), (x_ne, y_n));
                         }
 
                         if !self
                             .north_east(cell)
                             .is_some_and(|north_east| self.are_linked(cell, north_east))
                         {
-                            draw_antialiased_line_segment_mut(
-                                &mut img,
-                                (x_ne, y_n),
-                                (x_fe, y_m),
-                                WALL,
-                                pixelops::interpolate,
-                            );
+                            canvas.wall((x_ne, y_n), (x_fe, y_m));
                         }
                         if !self
                             .south_east(cell)
                             .is_some_and(|south_east| self.are_linked(cell, south_east))
                         {
-                            draw_antialiased_line_segment_mut(
-                                &mut img,
-                                (x_fe, y_m),
-                                (x_ne, y_s),
-                                WALL,
-                                pixelops::interpolate,
-                            );
+                            canvas.wall((x_fe, y_m), (x_ne, y_s));
                         }
                         if !self
                             .south(cell)
                             .is_some_and(|south| self.are_linked(cell, south))
                         {
-                            draw_antialiased_line_segment_mut(
-                                &mut img,
-                                (x_ne, y_s),
-                                (x_nw, y_s),
-                                WALL,
-                                pixelops::interpolate,
-                            );
+                            canvas.wall((x_ne, y_s), (x_nw, y_s));
                         }
                     }
                 }
             }
         }
 
-        img.save(format!("images/{file_name}.png"))
-            .expect("image to be saved");
+        self.draw_solution(&mut canvas, options, (cell_size / 4.0) as i32, |cell| {
+            let cx = cell_size + 3.0 * cell.col as f32 * a_size;
+            let mut cy = b_size + cell.row as f32 * height;
+            if cell.col % 2 == 1 {
+                cy += b_size;
+            }
+            (cx as i32, cy as i32)
+        });
+
+        canvas.finish()
     }
 }
 
@@ -1037,11 +1229,33 @@ impl Grid<Triangle> {
     }
 
     pub fn get(&self, row: isize, col: isize) -> Option<TriangleCell> {
-        let cell = TriangleCell { row, col };
-        self.links.contains_node(cell).then_some(cell)
+        self.cell_at(row, col, 0)
     }
 
     pub fn save_png(&self, file_name: &str, cell_size: u32) {
+        let options = RenderOptions::default();
+        self.draw::<Raster>(self.shading(&options), cell_size, 0.0, &options)
+            .save(format!("images/{file_name}.png"))
+            .expect("image to be saved");
+    }
+
+    pub fn save_svg(&self, file_name: &str, cell_size: u32) {
+        fs::write(format!("images/{file_name}.svg"), self.to_svg(cell_size))
+            .expect("svg to be saved");
+    }
+
+    pub fn to_svg(&self, cell_size: u32) -> String {
+        let options = RenderOptions::default();
+        self.draw::<Svg>(self.shading(&options), cell_size, 0.0, &options)
+    }
+
+    fn draw<C: Canvas>(
+        &self,
+        shading: Option<Shading<Triangle>>,
+        cell_size: u32,
+        _inset: f32,
+        options: &RenderOptions,
+    ) -> C::Output {
         let cell_size = cell_size as f32;
 
         let half_width = cell_size / 2.0;
@@ -1051,10 +1265,10 @@ impl Grid<Triangle> {
         let img_width = (cell_size * (self.num_cols() + 1) as f32 / 2.0) as u32;
         let img_height = (height * self.num_rows() as f32) as u32;
 
-        let mut img = RgbImage::from_pixel(img_width + 1, img_height + 1, BACKGROUND);
+        let mut canvas = C::new(img_width + 1, img_height + 1, options);
 
         let modes = [
-            self.distances().map(SavePngMode::Background),
+            self.background_mode(shading, options),
             Some(SavePngMode::Walls),
         ]
         .into_iter()
@@ -1075,10 +1289,9 @@ impl Grid<Triangle> {
                 };
 
                 match mode {
-                    SavePngMode::Background(ref distances) => {
-                        if let Some(color) = Self::background_for_cell(distances, cell) {
-                            draw_polygon_mut(
-                                &mut img,
+                    SavePngMode::Background(ref colors) => {
+                        if let Some(color) = colors.get(&cell).copied() {
+                            canvas.polygon(
                                 &[
                                     Point::new(west_x, base_y),
                                     Point::new(mid_x, apex_y),
@@ -1090,26 +1303,14 @@ impl Grid<Triangle> {
                     }
                     SavePngMode::Walls => {
                         if self.west(cell).is_none() {
-                            draw_antialiased_line_segment_mut(
-                                &mut img,
-                                (west_x, base_y),
-                                (mid_x, apex_y),
-                                WALL,
-                                pixelops::interpolate,
-                            );
+                            canvas.wall((west_x, base_y), (mid_x, apex_y));
                         }
 
                         if !self
                             .east(cell)
                             .is_some_and(|east| self.are_linked(cell, east))
                         {
-                            draw_antialiased_line_segment_mut(
-                                &mut img,
-                                (east_x, base_y),
-                                (mid_x, apex_y),
-                                WALL,
-                                pixelops::interpolate,
-                            );
+                            canvas.wall((east_x, base_y), (mid_x, apex_y));
                         }
 
                         let no_south = cell.is_upright() && self.south(cell).is_none();
@@ -1118,22 +1319,198 @@ impl Grid<Triangle> {
                                 .north(cell)
                                 .is_some_and(|north| self.are_linked(cell, north));
                         if no_south || not_linked {
-                            draw_antialiased_line_segment_mut(
-                                &mut img,
-                                (east_x, base_y),
-                                (west_x, base_y),
-                                WALL,
-                                pixelops::interpolate,
-                            );
+                            canvas.wall((east_x, base_y), (west_x, base_y));
                         }
                     }
                 }
             }
         }
 
-        img.save(format!("images/{file_name}.png"))
+        self.draw_solution(&mut canvas, options, (cell_size / 5.0) as i32, |cell| {
+            let cx = half_width + cell.col as f32 * half_width;
+            let cy = half_height + cell.row as f32 * height;
+            // the centroid sits a sixth of the height towards the base
+            let offset = if cell.is_upright() { height } else { -height } / 6.0;
+            (cx as i32, (cy + offset) as i32)
+        });
+
+        canvas.finish()
+    }
+}
+
+impl Grid<Upsilon> {
+    pub fn num_cols(&self) -> usize {
+        self.kind.cols
+    }
+
+    pub fn north(&self, cell: UpsilonCell) -> Option<UpsilonCell> {
+        self.get(cell.row - 1, cell.col)
+    }
+    pub fn south(&self, cell: UpsilonCell) -> Option<UpsilonCell> {
+        self.get(cell.row + 1, cell.col)
+    }
+    pub fn west(&self, cell: UpsilonCell) -> Option<UpsilonCell> {
+        self.get(cell.row, cell.col - 1)
+    }
+    pub fn east(&self, cell: UpsilonCell) -> Option<UpsilonCell> {
+        self.get(cell.row, cell.col + 1)
+    }
+
+    pub fn north_east(&self, cell: UpsilonCell) -> Option<UpsilonCell> {
+        self.diagonal(cell, -1, 1)
+    }
+    pub fn north_west(&self, cell: UpsilonCell) -> Option<UpsilonCell> {
+        self.diagonal(cell, -1, -1)
+    }
+    pub fn south_east(&self, cell: UpsilonCell) -> Option<UpsilonCell> {
+        self.diagonal(cell, 1, 1)
+    }
+    pub fn south_west(&self, cell: UpsilonCell) -> Option<UpsilonCell> {
+        self.diagonal(cell, 1, -1)
+    }
+
+    /// Only octagons have diagonal sides, which meet the octagons on their diagonals.
+    fn diagonal(&self, cell: UpsilonCell, rows: isize, cols: isize) -> Option<UpsilonCell> {
+        if !cell.is_octagon() {
+            return None;
+        }
+
+        self.get(cell.row + rows, cell.col + cols)
+    }
+
+    pub fn get(&self, row: isize, col: isize) -> Option<UpsilonCell> {
+        self.cell_at(row, col, 0)
+    }
+
+    pub fn save_png(&self, file_name: &str, cell_size: u32) {
+        let options = RenderOptions::default();
+        self.draw::<Raster>(self.shading(&options), cell_size, 0.0, &options)
+            .save(format!("images/{file_name}.png"))
             .expect("image to be saved");
     }
+
+    pub fn save_svg(&self, file_name: &str, cell_size: u32) {
+        fs::write(format!("images/{file_name}.svg"), self.to_svg(cell_size))
+            .expect("svg to be saved");
+    }
+
+    pub fn to_svg(&self, cell_size: u32) -> String {
+        let options = RenderOptions::default();
+        self.draw::<Svg>(self.shading(&options), cell_size, 0.0, &options)
+    }
+
+    /// Draws the octagons and squares with sides of `cell_size`, with the squares fitting into
+    /// the gaps left between four octagons.
+    fn draw<C: Canvas>(
+        &self,
+        shading: Option<Shading<Upsilon>>,
+        cell_size: u32,
+        _inset: f32,
+        options: &RenderOptions,
+    ) -> C::Output {
+        let side = cell_size as f32;
+        let half_side = side / 2.0;
+        // how far the diagonal sides of an octagon reach out past its square ones
+        let corner = side / 2.0_f32.sqrt();
+        let half_width = half_side + corner;
+        let spacing = side + corner;
+
+        let img_width = (spacing * (self.num_cols().max(1) - 1) as f32 + 2.0 * half_width) as u32;
+        let img_height = (spacing * (self.num_rows().max(1) - 1) as f32 + 2.0 * half_width) as u32;
+
+        let mut canvas = C::new(img_width + 1, img_height + 1, options);
+
+        let center = |cell: UpsilonCell| {
+            (
+                half_width + cell.col as f32 * spacing,
+                half_width + cell.row as f32 * spacing,
+            )
+        };
+
+        let modes = [
+            self.background_mode(shading, options),
+            Some(SavePngMode::Walls),
+        ]
+        .into_iter()
+        .flatten();
+        for mode in modes {
+            for cell in self.cells() {
+                let (cx, cy) = center(cell);
+                let point = |dx: f32, dy: f32| ((cx + dx) as i32, (cy + dy) as i32);
+
+                // corners clockwise from the top left, each side running from one corner to
+                // the next, so that sides and neighbours line up in the same order
+                let (corners, sides) = if cell.is_octagon() {
+                    let corners = vec![
+                        point(-half_side, -half_width),
+                        point(half_side, -half_width),
+                        point(half_width, -half_side),
+                        point(half_width, half_side),
+                        point(half_side, half_width),
+                        point(-half_side, half_width),
+                        point(-half_width, half_side),
+                        point(-half_width, -half_side),
+                    ];
+                    let sides = vec![
+                        (self.north(cell), false),
+                        (self.north_east(cell), false),
+                        (self.east(cell), true),
+                        (self.south_east(cell), true),
+                        (self.south(cell), true),
+                        (self.south_west(cell), true),
+                        (self.west(cell), false),
+                        (self.north_west(cell), false),
+                    ];
+                    (corners, sides)
+                } else {
+                    let corners = vec![
+                        point(-half_side, -half_side),
+                        point(half_side, -half_side),
+                        point(half_side, half_side),
+                        point(-half_side, half_side),
+                    ];
+                    let sides = vec![
+                        (self.north(cell), false),
+                        (self.east(cell), true),
+                        (self.south(cell), true),
+                        (self.west(cell), false),
+                    ];
+                    (corners, sides)
+                };
+
+                match mode {
+                    SavePngMode::Background(ref colors) => {
+                        if let Some(color) = colors.get(&cell).copied() {
+                            let points = corners
+                                .iter()
+                                .map(|(x, y)| Point::new(*x, *y))
+                                .collect::<Vec<_>>();
+                            canvas.polygon(&points, color);
+                        }
+                    }
+                    SavePngMode::Walls => {
+                        // sides shared with an earlier cell are drawn by that cell
+                        for (i, (neighbour, is_own)) in sides.into_iter().enumerate() {
+                            let draw = match neighbour {
+                                None => true,
+                                Some(other) => is_own && !self.are_linked(cell, other),
+                            };
+                            if draw {
+                                canvas.wall(corners[i], corners[(i + 1) % corners.len()]);
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        self.draw_solution(&mut canvas, options, (side / 4.0) as i32, |cell| {
+            let (cx, cy) = center(cell);
+            (cx as i32, cy as i32)
+        });
+
+        canvas.finish()
+    }
 }
 
 impl Grid<Weighted> {
@@ -1142,22 +1519,38 @@ impl Grid<Weighted> {
     }
 
     pub fn set_weight(&mut self, row: isize, col: isize, weight: usize) {
-        let cell = self
-            .links
-            .nodes()
-            .find(|n| n.row == row && n.col == col)
+        self.try_set_weight(row, col, weight)
             .expect("cell to be found");
-        let connected = self
-            .links
-            .edges(cell)
-            .map(|(_, other, _)| other)
-            .collect::<Vec<_>>();
-        self.links.remove_node(cell);
-
-        let cell = self.links.add_node(WeightedCell { row, col, weight });
-        for other in connected {
-            self.links.add_edge(cell, other, ());
+    }
+
+    /// Like [`Grid::set_weight`], but failing if there's no cell at `row` and `col`.
+    pub fn try_set_weight(
+        &mut self,
+        row: isize,
+        col: isize,
+        weight: usize,
+    ) -> Result<(), MazeError> {
+        let cell = self
+            .get(row, col)
+            .ok_or(MazeError::CellNotFound { row, col })?;
+        let weighted = WeightedCell { row, col, weight };
+
+        // packed passages are positional, so only the graph needs its node replaced
+        match &mut self.links {
+            Links::Packed(links) => links.replace(weighted),
+            Links::Graph(links) => {
+                self.index.insert(weighted);
+                // removing a node swaps the last one into its place, so moving that one back
+                // to the end leaves the new cell where the old was and the order unchanged
+                let last = links.from_index(links.node_count() - 1);
+                replace_node(links, cell, weighted);
+                if last != cell {
+                    replace_node(links, last, last);
+                }
+            }
         }
+
+        Ok(())
     }
 }
 
@@ -1180,9 +1573,7 @@ impl Grid<Weave> {
     }
 
     pub fn get(&self, row: isize, col: isize) -> Option<WeaveCell> {
-        self.links
-            .nodes()
-            .find(|n| n.is_over() && n.row() == row && n.col() == col)
+        self.cell_at(row, col, 0)
     }
 
     fn has_north_link(&self, cell: WeaveCell) -> bool {
@@ -1228,7 +1619,10 @@ impl Grid<Weave> {
         cell: OverCell,
         other: WeaveCell,
     ) -> WeaveCell {
-        let under = self.links.add_node(WeaveCell::Under(UnderCell::new(cell)));
+        let Links::Graph(links) = &mut self.links else {
+            unreachable!("weave grids are always backed by a graph")
+        };
+        let under = links.add_node(WeaveCell::Under(UnderCell::new(cell)));
 
         self.connect(under, initial);
         self.connect(under, other);
@@ -1237,16 +1631,44 @@ impl Grid<Weave> {
     }
 
     pub fn save_png(&self, file_name: &str, cell_size: u32, inset: f32) {
-        assert!(inset > 0.0, "inset must be non-zero for weave mazes");
+        let options = RenderOptions::default();
+        self.draw::<Raster>(self.shading(&options), cell_size, inset, &options)
+            .save(format!("images/{file_name}.png"))
+            .expect("image to be saved");
+    }
+
+    pub fn save_svg(&self, file_name: &str, cell_size: u32, inset: f32) {
+        fs::write(
+            format!("images/{file_name}.svg"),
+            self.to_svg(cell_size, inset),
+        )
+        .expect("svg to be saved");
+    }
+
+    pub fn to_svg(&self, cell_size: u32, inset: f32) -> String {
+        let options = RenderOptions::default();
+        self.draw::<Svg>(self.shading(&options), cell_size, inset, &options)
+    }
+
+    fn draw<C: Canvas>(
+        &self,
+        shading: Option<Shading<Weave>>,
+        cell_size: u32,
+        inset: f32,
+        options: &RenderOptions,
+    ) -> C::Output {
+        // crossings can't be seen without an inset, so any inset too small to show is drawn
+        // with a small one instead
+        let inset = if inset > 0.0 { inset } else { WEAVE_INSET };
 
         let img_width = cell_size * self.num_cols() as u32;
         let img_height = cell_size * self.num_rows() as u32;
-        let inset = (cell_size as f32 * inset) as u32;
+        let inset = ((cell_size as f32 * inset) as u32).max(1);
 
-        let mut img = RgbImage::from_pixel(img_width + 1, img_height + 1, BACKGROUND);
+        let mut canvas = C::new(img_width + 1, img_height + 1, options);
 
         let modes = [
-            self.distances().map(SavePngMode::Background),
+            self.background_mode(shading, options),
             Some(SavePngMode::Walls),
         ]
         .into_iter()
@@ -1259,7 +1681,7 @@ impl Grid<Weave> {
                 match cell {
                     WeaveCell::Over(_) => {
                         self.render_cell_with_inset(
-                            &mut img,
+                            &mut canvas,
                             cell,
                             &mode,
                             cell_size,
@@ -1268,7 +1690,7 @@ impl Grid<Weave> {
                         );
                     }
                     WeaveCell::Under(under) => self.render_under_cell_with_inset(
-                        &mut img,
+                        &mut canvas,
                         under,
                         &mode,
                         cell_size,
@@ -1279,13 +1701,20 @@ impl Grid<Weave> {
             }
         }
 
-        img.save(format!("images/{file_name}.png"))
-            .expect("image to be saved");
+        let half = cell_size as i32 / 2;
+        self.draw_solution(&mut canvas, options, half / 2, |cell| {
+            (
+                cell.col() as i32 * cell_size as i32 + half,
+                cell.row() as i32 * cell_size as i32 + half,
+            )
+        });
+
+        canvas.finish()
     }
 
     fn render_cell_with_inset(
         &self,
-        img: &mut ImageBuffer<Rgb<u8>, Vec<u8>>,
+        canvas: &mut impl Canvas,
         cell: WeaveCell,
         mode: &SavePngMode<Weave>,
         cell_size: u32,
@@ -1296,148 +1725,56 @@ impl Grid<Weave> {
             Self::cell_coordinates_with_inset(xy, cell_size, inset);
 
         match mode {
-            SavePngMode::Background(distances) => {
-                if let Some(color) = Self::background_for_cell(distances, cell) {
+            SavePngMode::Background(colors) => {
+                if let Some(color) = colors.get(&cell).copied() {
                     let inner_size = cell_size - 2 * inset;
 
-                    draw_filled_rect_mut(
-                        img,
-                        Rect::at(x2, y2).of_size(inner_size, inner_size),
-                        color,
-                    );
+                    canvas.rect(Rect::at(x2, y2).of_size(inner_size, inner_size), color);
 
                     if self.has_north_link(cell) {
-                        draw_filled_rect_mut(
-                            img,
-                            Rect::at(x2, y1).of_size(inner_size, inset),
-                            color,
-                        );
+                        canvas.rect(Rect::at(x2, y1).of_size(inner_size, inset), color);
                     }
 
                     if self.has_south_link(cell) {
-                        draw_filled_rect_mut(
-                            img,
-                            Rect::at(x2, y3).of_size(inner_size, inset),
-                            color,
-                        );
+                        canvas.rect(Rect::at(x2, y3).of_size(inner_size, inset), color);
                     }
 
                     if self.has_west_link(cell) {
-                        draw_filled_rect_mut(
-                            img,
-                            Rect::at(x1, y2).of_size(inset, inner_size),
-                            color,
-                        );
+                        canvas.rect(Rect::at(x1, y2).of_size(inset, inner_size), color);
                     }
 
                     if self.has_east_link(cell) {
-                        draw_filled_rect_mut(
-                            img,
-                            Rect::at(x3, y2).of_size(inset, inner_size),
-                            color,
-                        );
+                        canvas.rect(Rect::at(x3, y2).of_size(inset, inner_size), color);
                     }
                 }
             }
             SavePngMode::Walls => {
                 if self.has_north_link(cell) {
-                    draw_antialiased_line_segment_mut(
-                        img,
-                        (x2, y1),
-                        (x2, y2),
-                        WALL,
-                        pixelops::interpolate,
-                    );
-                    draw_antialiased_line_segment_mut(
-                        img,
-                        (x3, y1),
-                        (x3, y2),
-                        WALL,
-                        pixelops::interpolate,
-                    );
+                    canvas.wall((x2, y1), (x2, y2));
+                    canvas.wall((x3, y1), (x3, y2));
                 } else {
-                    draw_antialiased_line_segment_mut(
-                        img,
-                        (x2, y2),
-                        (x3, y2),
-                        WALL,
-                        pixelops::interpolate,
-                    );
+                    canvas.wall((x2, y2), (x3, y2));
                 }
 
                 if self.has_south_link(cell) {
-                    draw_antialiased_line_segment_mut(
-                        img,
-                        (x2, y3),
-                        (x2, y4),
-                        WALL,
-                        pixelops::interpolate,
-                    );
-                    draw_antialiased_line_segment_mut(
-                        img,
-                        (x3, y3),
-                        (x3, y4),
-                        WALL,
-                        pixelops::interpolate,
-                    );
+                    canvas.wall((x2, y3), (x2, y4));
+                    canvas.wall((x3, y3), (x3, y4));
                 } else {
-                    draw_antialiased_line_segment_mut(
-                        img,
-                        (x2, y3),
-                        (x3, y3),
-                        WALL,
-                        pixelops::interpolate,
-                    );
+                    canvas.wall((x2, y3), (x3, y3));
                 }
 
                 if self.has_west_link(cell) {
-                    draw_antialiased_line_segment_mut(
-                        img,
-                        (x1, y2),
-                        (x2, y2),
-                        WALL,
-                        pixelops::interpolate,
-                    );
-                    draw_antialiased_line_segment_mut(
-                        img,
-                        (x1, y3),
-                        (x2, y3),
-                        WALL,
-                        pixelops::interpolate,
-                    );
+                    canvas.wall((x1, y2), (x2, y2));
+                    canvas.wall((x1, y3), (x2, y3));
                 } else {
-                    draw_antialiased_line_segment_mut(
-                        img,
-                        (x2, y2),
-                        (x2, y3),
-                        WALL,
-                        pixelops::interpolate,
-                    );
+                    canvas.wall((x2, y2), (x2, y3));
                 }
 
                 if self.has_east_link(cell) {
-                    draw_antialiased_line_segment_mut(
-                        img,
-                        (x3, y2),
-                        (x4, y2),
-                        WALL,
-                        pixelops::interpolate,
-                    );
-                    draw_antialiased_line_segment_mut(
-                        img,
-                        (x3, y3),
-                        (x4, y3),
-                        WALL,
-                        pixelops::interpolate,
-                    );
+                    canvas.wall((x3, y2), (x4, y2));
+                    canvas.wall((x3, y3), (x4, y3));
                 } else {
-                    draw_antialiased_line_segment_mut(
-                        img,
-                        (x3, y2),
-                        (x3, y3),
-                        WALL,
-                        pixelops::interpolate,
-                    );
+                    canvas.wall((x3, y2), (x3, y3));
                 }
             }
         }
@@ -1445,7 +1782,7 @@ impl Grid<Weave> {
 
     fn render_under_cell_with_inset(
         &self,
-        img: &mut ImageBuffer<Rgb<u8>, Vec<u8>>,
+        canvas: &mut impl Canvas,
         cell: UnderCell,
         mode: &SavePngMode<Weave>,
         cell_size: u32,
@@ -1457,94 +1794,30 @@ impl Grid<Weave> {
         let is_vertical = self.is_vertical_passage(WeaveCell::Under(cell));
 
         match mode {
-            SavePngMode::Background(ref distances) => {
-                if let Some(color) = Self::background_for_cell(distances, WeaveCell::Under(cell)) {
+            SavePngMode::Background(colors) => {
+                if let Some(color) = colors.get(&WeaveCell::Under(cell)).copied() {
                     let inner_size = cell_size - 2 * inset;
 
                     if is_vertical {
-                        draw_filled_rect_mut(
-                            img,
-                            Rect::at(x2, y1).of_size(inner_size, inset),
-                            color,
-                        );
-                        draw_filled_rect_mut(
-                            img,
-                            Rect::at(x2, y3).of_size(inner_size, inset),
-                            color,
-                        );
+                        canvas.rect(Rect::at(x2, y1).of_size(inner_size, inset), color);
+                        canvas.rect(Rect::at(x2, y3).of_size(inner_size, inset), color);
                     } else {
-                        draw_filled_rect_mut(
-                            img,
-                            Rect::at(x1, y2).of_size(inset, inner_size),
-                            color,
-                        );
-                        draw_filled_rect_mut(
-                            img,
-                            Rect::at(x3, y2).of_size(inset, inner_size),
-                            color,
-                        );
+                        canvas.rect(Rect::at(x1, y2).of_size(inset, inner_size), color);
+                        canvas.rect(Rect::at(x3, y2).of_size(inset, inner_size), color);
                     }
                 }
             }
             SavePngMode::Walls => {
                 if is_vertical {
-                    draw_antialiased_line_segment_mut(
-                        img,
-                        (x2, y1),
-                        (x2, y2),
-                        WALL,
-                        pixelops::interpolate,
-                    );
-                    draw_antialiased_line_segment_mut(
-                        img,
-                        (x3, y1),
-                        (x3, y2),
-                        WALL,
-                        pixelops::interpolate,
-                    );
-                    draw_antialiased_line_segment_mut(
-                        img,
-                        (x2, y3),
-                        (x2, y4),
-                        WALL,
-                        pixelops::interpolate,
-                    );
-                    draw_antialiased_line_segment_mut(
-                        img,
-                        (x3, y3),
-                        (x3, y4),
-                        WALL,
-                        pixelops::interpolate,
-                    );
+                    canvas.wall((x2, y1), (x2, y2));
+                    canvas.wall((x3, y1), (x3, y2));
+                    canvas.wall((x2, y3), (x2, y4));
+                    canvas.wall((x3, y3), (x3, y4));
                 } else {
-                    draw_antialiased_line_segment_mut(
-                        img,
-                        (x1, y2),
-                        (x2, y2),
-                        WALL,
-                        pixelops::interpolate,
-                    );
-                    draw_antialiased_line_segment_mut(
-                        img,
-                        (x1, y3),
-                        (x2, y3),
-                        WALL,
-                        pixelops::interpolate,
-                    );
-                    draw_antialiased_line_segment_mut(
-                        img,
-                        (x3, y2),
-                        (x4, y2),
-                        WALL,
-                        pixelops::interpolate,
-                    );
-                    draw_antialiased_line_segment_mut(
-                        img,
-                        (x3, y3),
-                        (x4, y3),
-                        WALL,
-                        pixelops::interpolate,
-                    );
+                    canvas.wall((x1, y2), (x2, y2));
+                    canvas.wall((x1, y3), (x2, y3));
+                    canvas.wall((x3, y2), (x4, y2));
+                    canvas.wall((x3, y3), (x4, y3));
                 }
             }
         }
@@ -1595,9 +1868,7 @@ impl Grid<ThreeD> {
     }
 
     pub fn get(&self, row: isize, col: isize, level: isize) -> Option<ThreeDCell> {
-        self.links
-            .nodes()
-            .find(|n| n.row == row && n.col == col && n.level == level)
+        self.cell_at(row, col, level)
     }
 
     pub fn rows(&self) -> Vec<Vec<ThreeDCell>> {
@@ -1613,6 +1884,32 @@ impl Grid<ThreeD> {
     }
 
     pub fn save_png(&self, file_name: &str, cell_size: u32, inset: f32) {
+        let options = RenderOptions::default();
+        self.draw::<Raster>(self.shading(&options), cell_size, inset, &options)
+            .save(format!("images/{file_name}.png"))
+            .expect("image to be saved");
+    }
+
+    pub fn save_svg(&self, file_name: &str, cell_size: u32, inset: f32) {
+        fs::write(
+            format!("images/{file_name}.svg"),
+            self.to_svg(cell_size, inset),
+        )
+        .expect("svg to be saved");
+    }
+
+    pub fn to_svg(&self, cell_size: u32, inset: f32) -> String {
+        let options = RenderOptions::default();
+        self.draw::<Svg>(self.shading(&options), cell_size, inset, &options)
+    }
+
+    fn draw<C: Canvas>(
+        &self,
+        shading: Option<Shading<ThreeD>>,
+        cell_size: u32,
+        inset: f32,
+        options: &RenderOptions,
+    ) -> C::Output {
         let margin = cell_size / 2;
         let inset = (cell_size as f32 * inset) as u32;
 
@@ -1623,10 +1920,10 @@ impl Grid<ThreeD> {
         let img_width = grid_width * levels + (levels - 1) * margin;
         let img_height = grid_height;
 
-        let mut img = RgbImage::from_pixel(img_width + 1, img_height + 1, BACKGROUND);
+        let mut canvas = C::new(img_width + 1, img_height + 1, options);
 
         let modes = [
-            self.distances().map(SavePngMode::Background),
+            self.background_mode(shading, options),
             Some(SavePngMode::Walls),
         ]
         .into_iter()
@@ -1638,9 +1935,9 @@ impl Grid<ThreeD> {
                 let y = cell.row as i32 * cell_size as i32;
 
                 if inset > 0 {
-                    self.render_cell_with_inset(&mut img, cell, &mode, cell_size, (x, y), inset);
+                    self.render_cell_with_inset(&mut canvas, cell, &mode, cell_size, (x, y), inset);
                 } else {
-                    self.render_cell_without_inset(&mut img, cell, &mode, cell_size, (x, y));
+                    self.render_cell_without_inset(&mut canvas, cell, &mode, cell_size, (x, y));
                 }
 
                 if matches!(mode, SavePngMode::Walls) {
@@ -1651,54 +1948,514 @@ impl Grid<ThreeD> {
                         .down(cell)
                         .is_some_and(|down| self.are_linked(cell, down))
                     {
-                        draw_antialiased_line_segment_mut(
-                            &mut img,
-                            (mid_x - 3, mid_y),
-                            (mid_x - 1, mid_y + 2),
-                            RED,
-                            pixelops::interpolate,
-                        );
-                        draw_antialiased_line_segment_mut(
-                            &mut img,
-                            (mid_x - 3, mid_y),
-                            (mid_x - 1, mid_y - 2),
-                            RED,
-                            pixelops::interpolate,
-                        );
+                        canvas.line((mid_x - 3, mid_y), (mid_x - 1, mid_y + 2), RED);
+                        canvas.line((mid_x - 3, mid_y), (mid_x - 1, mid_y - 2), RED);
                     }
 
                     if self.up(cell).is_some_and(|up| self.are_linked(cell, up)) {
-                        draw_antialiased_line_segment_mut(
-                            &mut img,
-                            (mid_x + 3, mid_y),
-                            (mid_x + 1, mid_y + 2),
-                            RED,
-                            pixelops::interpolate,
-                        );
-                        draw_antialiased_line_segment_mut(
-                            &mut img,
-                            (mid_x + 3, mid_y),
-                            (mid_x + 1, mid_y - 2),
-                            RED,
-                            pixelops::interpolate,
-                        );
+                        canvas.line((mid_x + 3, mid_y), (mid_x + 1, mid_y + 2), RED);
+                        canvas.line((mid_x + 3, mid_y), (mid_x + 1, mid_y - 2), RED);
                     }
                 }
             }
         }
 
-        img.save(format!("images/{file_name}.png"))
-            .expect("image to be saved");
+        let half = cell_size as i32 / 2;
+        self.draw_solution(&mut canvas, options, half / 2, |cell| {
+            (
+                cell.level as i32 * (grid_width + margin) as i32
+                    + cell.col as i32 * cell_size as i32
+                    + half,
+                cell.row as i32 * cell_size as i32 + half,
+            )
+        });
+
+        canvas.finish()
     }
 
     save_png_inset_helpers!(ThreeD);
 }
 
+/// Where each face of a cube is drawn in its unfolded net, as the column and row of faces: the
+/// top above the front, with the left, front, right and back in a band and the bottom below.
+const CUBE_NET: [(i32, i32); 6] = [(1, 0), (0, 1), (1, 1), (2, 1), (3, 1), (1, 2)];
+
+impl Grid<Cube> {
+    pub fn face_size(&self) -> usize {
+        self.kind.size
+    }
+
+    pub fn north(&self, cell: CubeCell) -> Option<CubeCell> {
+        cell.step(self.face_size(), 0, -1)
+    }
+    pub fn south(&self, cell: CubeCell) -> Option<CubeCell> {
+        cell.step(self.face_size(), 0, 1)
+    }
+    pub fn west(&self, cell: CubeCell) -> Option<CubeCell> {
+        cell.step(self.face_size(), -1, 0)
+    }
+    pub fn east(&self, cell: CubeCell) -> Option<CubeCell> {
+        cell.step(self.face_size(), 1, 0)
+    }
+
+    pub fn get(&self, face: isize, row: isize, col: isize) -> Option<CubeCell> {
+        self.cell_at(row, col, face)
+    }
+
+    pub fn save_png(&self, file_name: &str, cell_size: u32) {
+        let options = RenderOptions::default();
+        self.draw::<Raster>(self.shading(&options), cell_size, 0.0, &options)
+            .save(format!("images/{file_name}.png"))
+            .expect("image to be saved");
+    }
+
+    pub fn save_svg(&self, file_name: &str, cell_size: u32) {
+        fs::write(format!("images/{file_name}.svg"), self.to_svg(cell_size))
+            .expect("svg to be saved");
+    }
+
+    pub fn to_svg(&self, cell_size: u32) -> String {
+        let options = RenderOptions::default();
+        self.draw::<Svg>(self.shading(&options), cell_size, 0.0, &options)
+    }
+
+    /// The top left corner of `cell` in the unfolded net.
+    fn net_position(&self, cell: CubeCell, cell_size: u32) -> (i32, i32) {
+        let (face_col, face_row) = CUBE_NET[cell.face as usize];
+        let size = self.face_size() as i32;
+        let cell_size = cell_size as i32;
+
+        (
+            (face_col * size + cell.col as i32) * cell_size,
+            (face_row * size + cell.row as i32) * cell_size,
+        )
+    }
+
+    /// Draws the six faces unfolded into a cross. Edges that meet in the net are drawn once,
+    /// while the ones that only meet once folded are left open on both sides wherever a
+    /// passage runs over them.
+    fn draw<C: Canvas>(
+        &self,
+        shading: Option<Shading<Cube>>,
+        cell_size: u32,
+        _inset: f32,
+        options: &RenderOptions,
+    ) -> C::Output {
+        let face_size = cell_size * self.face_size() as u32;
+        let mut canvas = C::new(4 * face_size + 1, 3 * face_size + 1, options);
+        let step = cell_size as i32;
+
+        let modes = [
+            self.background_mode(shading, options),
+            Some(SavePngMode::Walls),
+        ]
+        .into_iter()
+        .flatten();
+        for mode in modes {
+            for cell in self.cells() {
+                let (x1, y1) = self.net_position(cell, cell_size);
+                let (x2, y2) = (x1 + step, y1 + step);
+
+                match &mode {
+                    SavePngMode::Background(colors) => {
+                        if let Some(color) = colors.get(&cell).copied() {
+                            canvas.rect(Rect::at(x1, y1).of_size(cell_size, cell_size), color);
+                        }
+                    }
+                    SavePngMode::Walls => {
+                        // a neighbour drawn right next to the cell draws their shared wall from
+                        // its own side
+                        let is_drawn_at = |other: CubeCell, at: (i32, i32)| {
+                            self.net_position(other, cell_size) == at
+                        };
+                        let north = self.north(cell).filter(|north| {
+                            !self.are_linked(cell, *north) && !is_drawn_at(*north, (x1, y1 - step))
+                        });
+                        let west = self.west(cell).filter(|west| {
+                            !self.are_linked(cell, *west) && !is_drawn_at(*west, (x1 - step, y1))
+                        });
+
+                        if north.is_some() {
+                            canvas.wall((x1, y1), (x2, y1));
+                        }
+                        if west.is_some() {
+                            canvas.wall((x1, y1), (x1, y2));
+                        }
+                        if !self
+                            .east(cell)
+                            .is_some_and(|east| self.are_linked(cell, east))
+                        {
+                            canvas.wall((x2, y1), (x2, y2));
+                        }
+                        if !self
+                            .south(cell)
+                            .is_some_and(|south| self.are_linked(cell, south))
+                        {
+                            canvas.wall((x1, y2), (x2, y2));
+                        }
+                    }
+                }
+            }
+        }
+
+        let half = step / 2;
+        self.draw_solution_with_breaks(
+            &mut canvas,
+            options,
+            half / 2,
+            |cell| {
+                let (x, y) = self.net_position(cell, cell_size);
+                (x + half, y + half)
+            },
+            |cell, next| {
+                let (x1, y1) = self.net_position(cell, cell_size);
+                let (x2, y2) = self.net_position(next, cell_size);
+                x1.abs_diff(x2) + y1.abs_diff(y2) != cell_size
+            },
+        );
+
+        canvas.finish()
+    }
+}
+
+impl<K: Wrapping> Grid<K> {
+    pub fn num_cols(&self) -> usize {
+        self.kind.num_cols()
+    }
+
+    pub fn north(&self, cell: RegularCell) -> Option<RegularCell> {
+        self.wrap_down(cell.row - 1, cell.col)
+    }
+    pub fn south(&self, cell: RegularCell) -> Option<RegularCell> {
+        self.wrap_down(cell.row + 1, cell.col)
+    }
+    pub fn west(&self, cell: RegularCell) -> Option<RegularCell> {
+        self.wrap_across(cell.row, cell.col - 1)
+    }
+    pub fn east(&self, cell: RegularCell) -> Option<RegularCell> {
+        self.wrap_across(cell.row, cell.col + 1)
+    }
+
+    pub fn get(&self, row: isize, col: isize) -> Option<RegularCell> {
+        self.cell_at(row, col, 0)
+    }
+
+    /// The cell at `row` and `col`, where `col` may have run off the west or east edge.
+    fn wrap_across(&self, row: isize, col: isize) -> Option<RegularCell> {
+        let cols = self.num_cols() as isize;
+        match K::ACROSS {
+            _ if (0..cols).contains(&col) => self.get(row, col),
+            Join::Open => None,
+            Join::Straight => self.get(row, col.rem_euclid(cols)),
+            Join::Twisted => self.get(self.num_rows() as isize - 1 - row, col.rem_euclid(cols)),
+        }
+    }
+
+    /// The cell at `row` and `col`, where `row` may have run off the north or south edge.
+    fn wrap_down(&self, row: isize, col: isize) -> Option<RegularCell> {
+        let rows = self.num_rows() as isize;
+        match K::DOWN {
+            _ if (0..rows).contains(&row) => self.get(row, col),
+            Join::Open => None,
+            Join::Straight => self.get(row.rem_euclid(rows), col),
+            Join::Twisted => self.get(row.rem_euclid(rows), self.num_cols() as isize - 1 - col),
+        }
+    }
+
+    pub fn save_png(&self, file_name: &str, cell_size: u32, inset: f32) {
+        let options = RenderOptions::default();
+        self.draw::<Raster>(self.shading(&options), cell_size, inset, &options)
+            .save(format!("images/{file_name}.png"))
+            .expect("image to be saved");
+    }
+
+    pub fn save_svg(&self, file_name: &str, cell_size: u32, inset: f32) {
+        fs::write(
+            format!("images/{file_name}.svg"),
+            self.to_svg(cell_size, inset),
+        )
+        .expect("svg to be saved");
+    }
+
+    pub fn to_svg(&self, cell_size: u32, inset: f32) -> String {
+        let options = RenderOptions::default();
+        self.draw::<Svg>(self.shading(&options), cell_size, inset, &options)
+    }
+
+    /// Draws the grid flat, with its joined edges left open wherever a passage runs across them
+    /// and arrows beside them showing which way round they meet. Edges that meet straight have
+    /// arrows pointing the same way, and twisted ones opposite ways. The north and south edges
+    /// get double arrows to tell them apart from the west and east ones.
+    fn draw<C: Canvas>(
+        &self,
+        shading: Option<Shading<K>>,
+        cell_size: u32,
+        inset: f32,
+        options: &RenderOptions,
+    ) -> C::Output {
+        let margin = cell_size as i32 / 2;
+        let inset = (cell_size as f32 * inset) as u32;
+
+        let grid_width = (cell_size * self.num_cols() as u32) as i32;
+        let grid_height = (cell_size * self.num_rows() as u32) as i32;
+
+        let mut canvas = C::new(
+            (grid_width + 2 * margin + 1) as u32,
+            (grid_height + 2 * margin + 1) as u32,
+            options,
+        );
+
+        let modes = [
+            self.background_mode(shading, options),
+            Some(SavePngMode::Walls),
+        ]
+        .into_iter()
+        .flatten();
+        for mode in modes {
+            for cell in self.cells() {
+                let x = margin + cell.col as i32 * cell_size as i32;
+                let y = margin + cell.row as i32 * cell_size as i32;
+
+                if inset > 0 {
+                    self.render_cell_with_inset(&mut canvas, cell, &mode, cell_size, (x, y), inset);
+                } else {
+                    self.render_cell_without_inset(&mut canvas, cell, &mode, cell_size, (x, y));
+                }
+            }
+        }
+
+        let (left, top) = (margin / 2, margin / 2);
+        let (right, bottom) = (
+            grid_width + margin + margin / 2,
+            grid_height + margin + margin / 2,
+        );
+        let (middle_x, middle_y) = (margin + grid_width / 2, margin + grid_height / 2);
+        let length = cell_size as i32;
+        let flip = |join| if join == Join::Twisted { -1 } else { 1 };
+        if K::ACROSS != Join::Open {
+            let mut down = |x, sign: i32| {
+                let from = (x, middle_y - sign * length / 2);
+                let to = (x, middle_y + sign * length / 2);
+                draw_arrow(&mut canvas, from, to, 1, cell_size);
+            };
+            down(left, 1);
+            down(right, flip(K::ACROSS));
+        }
+        if K::DOWN != Join::Open {
+            let mut across = |y, sign: i32| {
+                let from = (middle_x - sign * length / 2, y);
+                let to = (middle_x + sign * length / 2, y);
+                draw_arrow(&mut canvas, from, to, 2, cell_size);
+            };
+            across(top, 1);
+            across(bottom, flip(K::DOWN));
+        }
+
+        let half = cell_size as i32 / 2;
+        self.draw_solution_with_breaks(
+            &mut canvas,
+            options,
+            half / 2,
+            |cell| {
+                (
+                    margin + cell.col as i32 * cell_size as i32 + half,
+                    margin + cell.row as i32 * cell_size as i32 + half,
+                )
+            },
+            |cell, next| cell.row.abs_diff(next.row) + cell.col.abs_diff(next.col) != 1,
+        );
+
+        canvas.finish()
+    }
+
+    save_png_inset_helpers!(K);
+}
+
+/// Swaps `cell` for `with` in the graph, keeping its passages and moving it to the end.
+fn replace_node<C: CellKind>(links: &mut UnGraphMap<C, ()>, cell: C, with: C) {
+    let connected = links
+        .edges(cell)
+        .map(|(_, other, _)| other)
+        .collect::<Vec<_>>();
+    links.remove_node(cell);
+
+    links.add_node(with);
+    for other in connected {
+        links.add_edge(with, other, ());
+    }
+}
+
+/// Draws a red arrow from `from` to `to`, which must line up along one axis, with `heads`
+/// chevrons at its tip.
+fn draw_arrow(
+    canvas: &mut impl Canvas,
+    from: (i32, i32),
+    to: (i32, i32),
+    heads: i32,
+    cell_size: u32,
+) {
+    canvas.line(from, to, RED);
+
+    let size = (cell_size as i32 / 6).max(2);
+    let (dx, dy) = ((to.0 - from.0).signum(), (to.1 - from.1).signum());
+    for head in 0..heads {
+        let tip = (to.0 - dx * head * size, to.1 - dy * head * size);
+        let back = (tip.0 - dx * size, tip.1 - dy * size);
+        canvas.line(tip, (back.0 - dy * size, back.1 + dx * size), RED);
+        canvas.line(tip, (back.0 + dy * size, back.1 - dx * size), RED);
+    }
+}
+
+impl_render!(Cube, Polar, Hex, Triangle, Upsilon, Weave, ThreeD);
+impl_render!(impl<K: Wrapping> K);
+
+/// How the background of each cell is coloured.
+pub enum Shading<K: Kind> {
+    /// Shades cells by distance with [`RenderOptions::gradient`], and weighted cells with
+    /// [`RenderOptions::weighted`].
+    Distances(Distances<K>),
+    Highlight(FxHashMap<K::Cell, Rgb<u8>>),
+}
+
+/// Kinds whose grids can be drawn as an image.
+pub trait Render: Kind {
+    /// Draws `grid` with square cells of `cell_size` pixels, shrinking them by `inset` (as a
+    /// fraction of the cell size) where the kind supports it.
+    fn render(
+        grid: &Grid<Self>,
+        shading: Option<Shading<Self>>,
+        cell_size: u32,
+        inset: f32,
+        options: &RenderOptions,
+    ) -> RgbImage;
+
+    /// Same as [`Render::render`], but as an SVG document with the walls as paths.
+    fn render_svg(
+        grid: &Grid<Self>,
+        shading: Option<Shading<Self>>,
+        cell_size: u32,
+        inset: f32,
+        options: &RenderOptions,
+    ) -> String;
+}
+
+impl<K: Render> Grid<K> {
+    /// The maze as it would be saved by `save_png`, without touching the filesystem.
+    pub fn to_image(&self, cell_size: u32, inset: f32, options: &RenderOptions) -> RgbImage {
+        K::render(self, self.shading(options), cell_size, inset, options)
+    }
+
+    pub fn write_png(
+        &self,
+        writer: impl Write,
+        cell_size: u32,
+        inset: f32,
+        options: &RenderOptions,
+    ) -> Result<(), MazeError> {
+        let img = self.to_image(cell_size, inset, options);
+        PngEncoder::new(writer).write_image(
+            img.as_raw(),
+            img.width(),
+            img.height(),
+            ColorType::Rgb8,
+        )?;
+
+        Ok(())
+    }
+
+    pub fn save_png_to(
+        &self,
+        path: impl AsRef<Path>,
+        cell_size: u32,
+        inset: f32,
+        options: &RenderOptions,
+    ) -> Result<(), MazeError> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        self.write_png(&mut writer, cell_size, inset, options)?;
+        writer.flush()?;
+
+        Ok(())
+    }
+
+    pub fn write_svg(
+        &self,
+        mut writer: impl Write,
+        cell_size: u32,
+        inset: f32,
+        options: &RenderOptions,
+    ) -> Result<(), MazeError> {
+        let svg = K::render_svg(self, self.shading(options), cell_size, inset, options);
+        writer.write_all(svg.as_bytes())?;
+
+        Ok(())
+    }
+
+    pub fn save_svg_to(
+        &self,
+        path: impl AsRef<Path>,
+        cell_size: u32,
+        inset: f32,
+        options: &RenderOptions,
+    ) -> Result<(), MazeError> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        self.write_svg(&mut writer, cell_size, inset, options)?;
+        writer.flush()?;
+
+        Ok(())
+    }
+}
+
 enum SavePngMode<K: Kind> {
-    Background(Distances<K>),
+    Background(FxHashMap<K::Cell, Rgb<u8>>),
     Walls,
 }
 
-pub(crate) fn row_len(links: &UnGraphMap<impl CellKind, ()>, r: isize) -> usize {
-    links.nodes().filter(|c| c.row() == r).count()
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use super::*;
+    use crate::generators::KruskalsBetterWeave;
+
+    #[test]
+    fn solutions_stay_joined_across_levels() {
+        let mut grid = Grid::new(ThreeD::new(1, 1, 2), None, None);
+        let (lower, upper) = (ThreeDCell::new(0, 0, 0), ThreeDCell::new(0, 0, 1));
+        grid.link(lower, upper);
+        grid.set_start(lower);
+        grid.set_goal(upper);
+
+        let blue = Rgb([0, 0, 255]);
+        let options = RenderOptions {
+            solution: Some(blue),
+            shade: false,
+            ..RenderOptions::default()
+        };
+        // levels 20 pixels wide with a 10 pixel gap, so this is between the two panels
+        let img = grid.to_image(20, 0.0, &options);
+        assert_eq!(*img.get_pixel(25, 10), blue);
+    }
+
+    #[test]
+    fn flat_weaves_are_drawn_with_the_fallback_inset_everywhere() {
+        let grid = Grid::new(Weave::new(6, 6), None, None)
+            .generate(&KruskalsBetterWeave, &mut StdRng::seed_from_u64(0));
+        let options = RenderOptions::default();
+        let expected = grid.to_image(10, WEAVE_INSET, &options);
+
+        assert_eq!(grid.to_image(10, 0.0, &options), expected);
+
+        let mut png = Vec::new();
+        grid.write_png(&mut png, 10, 0.0, &options)
+            .expect("png to be written");
+        let written = image::load_from_memory(&png).expect("png to be decoded");
+        assert_eq!(written.to_rgb8(), expected);
+
+        let (mut flat, mut inset) = (Vec::new(), Vec::new());
+        grid.write_svg(&mut flat, 10, 0.0, &options)
+            .expect("svg to be written");
+        grid.write_svg(&mut inset, 10, WEAVE_INSET, &options)
+            .expect("svg to be written");
+        assert_eq!(flat, inset);
+    }
 }
@@ -0,0 +1,76 @@
+use crate::cell::CellKind;
+
+/// Dense lookup table from a cell's position to the cell stored in the grid.
+///
+/// Rows are stored level by level, so a cell lives at
+/// `rows[level * rows_per_level + row][col]`.
+#[derive(Clone)]
+pub(crate) struct CellIndex<C: CellKind> {
+    rows_per_level: usize,
+    rows: Vec<Vec<Option<C>>>,
+    row_lens: Vec<usize>,
+}
+
+impl<C: CellKind> CellIndex<C> {
+    pub(crate) fn new(cells: impl Iterator<Item = C>) -> Self {
+        let cells = cells.collect::<Vec<_>>();
+        let rows_per_level = cells
+            .iter()
+            .map(|cell| cell.row() as usize + 1)
+            .max()
+            .unwrap_or(0);
+
+        let mut index = Self {
+            rows_per_level,
+            rows: Vec::new(),
+            row_lens: Vec::new(),
+        };
+        for cell in cells {
+            index.insert(cell);
+        }
+
+        index
+    }
+
+    pub(crate) fn get(&self, row: isize, col: isize, level: isize) -> Option<C> {
+        let idx = self.row_idx(row, level)?;
+        let col = usize::try_from(col).ok()?;
+
+        self.rows.get(idx)?.get(col).copied().flatten()
+    }
+
+    /// Stores `cell` at its position, replacing whatever was there before.
+    pub(crate) fn insert(&mut self, cell: C) {
+        let idx = self
+            .row_idx(cell.row(), cell.level())
+            .expect("cell position to be non-negative");
+        let col = usize::try_from(cell.col()).expect("cell position to be non-negative");
+
+        if self.rows.len() <= idx {
+            self.rows.resize_with(idx + 1, Vec::new);
+            self.row_lens.resize(idx + 1, 0);
+        }
+        let row = &mut self.rows[idx];
+        if row.len() <= col {
+            row.resize(col + 1, None);
+        }
+        if row[col].replace(cell).is_none() {
+            self.row_lens[idx] += 1;
+        }
+    }
+
+    /// Number of cells on the given row of the first level.
+    pub(crate) fn row_len(&self, row: isize) -> usize {
+        self.row_idx(row, 0)
+            .and_then(|idx| self.row_lens.get(idx))
+            .copied()
+            .unwrap_or(0)
+    }
+
+    fn row_idx(&self, row: isize, level: isize) -> Option<usize> {
+        let row = usize::try_from(row).ok()?;
+        let level = usize::try_from(level).ok()?;
+
+        (row < self.rows_per_level).then_some(level * self.rows_per_level + row)
+    }
+}
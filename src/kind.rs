@@ -4,13 +4,19 @@ use petgraph::prelude::UnGraphMap;
 
 use crate::{
     cell::{
-        CellKind, HexCell, PolarCell, RegularCell, ThreeDCell, TriangleCell, WeaveCell,
-        WeightedCell,
+        CellKind, CubeCell, HexCell, PolarCell, RegularCell, ThreeDCell, TriangleCell, UpsilonCell,
+        WeaveCell, WeightedCell,
     },
-    grid::{row_len, Grid},
+    generators::{
+        self, BinaryTree, Ellers, Generator, KruskalsBetterWeave, RecursiveDivision, Sidewinder,
+    },
+    grid::Grid,
     mask::Mask,
+    path::Direction,
 };
 
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Regular {
     pub rows: usize,
     pub cols: usize,
@@ -22,6 +28,8 @@ impl Regular {
     }
 }
 
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Masked(pub Mask);
 
 impl Masked {
@@ -30,6 +38,8 @@ impl Masked {
     }
 }
 
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Polar {
     pub rows: usize,
 }
@@ -40,6 +50,8 @@ impl Polar {
     }
 }
 
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Hex {
     pub rows: usize,
     pub cols: usize,
@@ -51,6 +63,8 @@ impl Hex {
     }
 }
 
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Triangle {
     pub rows: usize,
     pub cols: usize,
@@ -62,6 +76,23 @@ impl Triangle {
     }
 }
 
+/// Octagons and squares in a checkerboard, where octagons meet the squares around them and
+/// the octagons on their diagonals, while squares only meet the octagons around them.
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Upsilon {
+    pub rows: usize,
+    pub cols: usize,
+}
+
+impl Upsilon {
+    pub fn new(rows: usize, cols: usize) -> Self {
+        Self { rows, cols }
+    }
+}
+
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Weighted {
     pub rows: usize,
     pub cols: usize,
@@ -73,9 +104,12 @@ impl Weighted {
     }
 }
 
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Weave {
     pub rows: usize,
     pub cols: usize,
+    #[cfg_attr(feature = "serde", serde(default))]
     pub is_preconfigured: bool,
 }
 
@@ -89,6 +123,8 @@ impl Weave {
     }
 }
 
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ThreeD {
     pub rows: usize,
     pub cols: usize,
@@ -101,9 +137,82 @@ impl ThreeD {
     }
 }
 
+/// The surface of a cube, with six faces of `size` by `size` cells whose edges join up with the
+/// faces around them. Unlike [`ThreeD`], every cell is on the outside.
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Cube {
+    pub size: usize,
+}
+
+impl Cube {
+    pub fn new(size: usize) -> Self {
+        Self { size }
+    }
+}
+
+/// A grid whose west and east edges are joined, so that walking off one side comes back on the
+/// other.
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Cylinder {
+    pub rows: usize,
+    pub cols: usize,
+}
+
+impl Cylinder {
+    pub fn new(rows: usize, cols: usize) -> Self {
+        Self { rows, cols }
+    }
+}
+
+/// A grid whose opposite edges are both joined, like the screen of an old arcade game.
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Torus {
+    pub rows: usize,
+    pub cols: usize,
+}
+
+impl Torus {
+    pub fn new(rows: usize, cols: usize) -> Self {
+        Self { rows, cols }
+    }
+}
+
+/// A grid whose west and east edges are joined with a half twist, so that walking off the east
+/// edge in the top row comes back on the west edge in the bottom row.
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MobiusStrip {
+    pub rows: usize,
+    pub cols: usize,
+}
+
+impl MobiusStrip {
+    pub fn new(rows: usize, cols: usize) -> Self {
+        Self { rows, cols }
+    }
+}
+
+/// A grid joined like a [`MobiusStrip`] across and like a [`Cylinder`] down, so that the north
+/// and south edges meet as well.
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct KleinBottle {
+    pub rows: usize,
+    pub cols: usize,
+}
+
+impl KleinBottle {
+    pub fn new(rows: usize, cols: usize) -> Self {
+        Self { rows, cols }
+    }
+}
+
 pub trait Kind
 where
-    Self: Sized,
+    Self: Sized + 'static,
 {
     type Cell: CellKind;
 
@@ -115,6 +224,221 @@ where
         grid.connect(cell, other);
     }
     fn neighbours(&self, grid: &Grid<Self>, cell: Self::Cell) -> impl Iterator<Item = Self::Cell>;
+
+    /// The generators that can carve a grid of this kind.
+    fn generators() -> Vec<Box<dyn Generator<Self>>> {
+        generators::generic()
+    }
+
+    /// A lower bound on the number of steps from `cell` to `goal`, used to guide
+    /// [`AStar`](crate::solvers::AStar). Defaults to the Manhattan distance over rows, columns
+    /// and levels.
+    fn heuristic(&self, cell: Self::Cell, goal: Self::Cell) -> usize {
+        cell.row().abs_diff(goal.row())
+            + cell.col().abs_diff(goal.col())
+            + cell.level().abs_diff(goal.level())
+    }
+
+    /// Which way `to` lies from `from`, if they are neighbours. Defaults to the compass points
+    /// and levels of a rectangular grid.
+    fn direction(_grid: &Grid<Self>, from: Self::Cell, to: Self::Cell) -> Option<Direction> {
+        let offset = (
+            to.level() - from.level(),
+            to.row() - from.row(),
+            to.col() - from.col(),
+        );
+
+        match offset {
+            (0, -1, 0) => Some(Direction::North),
+            (0, 1, 0) => Some(Direction::South),
+            (0, 0, 1) => Some(Direction::East),
+            (0, 0, -1) => Some(Direction::West),
+            (1, 0, 0) => Some(Direction::Up),
+            (-1, 0, 0) => Some(Direction::Down),
+            _ => None,
+        }
+    }
+
+    /// Whether a way through `cell` in from `from` and out to `to` carries straight on rather
+    /// than turning. Defaults to the two lying in opposite directions.
+    fn is_straight(grid: &Grid<Self>, cell: Self::Cell, from: Self::Cell, to: Self::Cell) -> bool {
+        let back = Self::direction(grid, cell, from);
+        back.is_some() && back.map(Direction::opposite) == Self::direction(grid, cell, to)
+    }
+
+    /// The cell a passage between `cell` and `other` would cross, for kinds whose passages can
+    /// tunnel under a cell to the one beyond it. Defaults to none.
+    fn crossing(_grid: &Grid<Self>, _cell: Self::Cell, _other: Self::Cell) -> Option<Self::Cell> {
+        None
+    }
+
+    /// Whether a passage between `cell` and `other` makes sense for this kind. Defaults to them
+    /// being neighbours.
+    fn is_valid_link(grid: &Grid<Self>, cell: Self::Cell, other: Self::Cell) -> bool {
+        Self::direction(grid, cell, other).is_some()
+    }
+}
+
+/// Kinds whose cells sit on a box of rows, columns and levels, so their passages can be packed
+/// into wall bits with [`Grid::new_packed`].
+pub trait Rectangular: Kind {
+    fn num_cols(&self) -> usize;
+    fn num_levels(&self) -> usize {
+        1
+    }
+    /// The cell a fresh grid has at a position within its rows, columns and levels, if any.
+    fn cell_at(&self, row: isize, col: isize, level: isize) -> Option<Self::Cell>;
+}
+
+/// How a pair of opposite edges of a [`Wrapping`] grid is joined.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Join {
+    /// The edges are the outside of the maze.
+    Open,
+    /// Walking off one edge comes back on the other in the same row or column.
+    Straight,
+    /// Walking off one edge comes back on the other in the mirrored row or column.
+    Twisted,
+}
+
+/// Kinds of rectangular grid whose opposite edges are joined, so that their neighbours carry
+/// on around the surface. Rows and columns count from the north west corner as drawn.
+pub trait Wrapping: Kind<Cell = RegularCell> {
+    /// How the west and east edges are joined.
+    const ACROSS: Join;
+    /// How the north and south edges are joined.
+    const DOWN: Join;
+
+    fn num_cols(&self) -> usize;
+}
+
+/// Kinds whose cells have their neighbours in a clockwise order, so that a walker in the maze
+/// can tell left from right.
+pub trait Heading: Kind {
+    /// The cells around `cell` in clockwise order as drawn, starting from any of them.
+    fn around(grid: &Grid<Self>, cell: Self::Cell) -> Vec<Self::Cell>;
+}
+
+macro_rules! rectangular_heading {
+    ($($T:ty),+ $(,)?) => {
+        $(
+            impl Heading for $T {
+                fn around(grid: &Grid<Self>, cell: Self::Cell) -> Vec<Self::Cell> {
+                    [
+                        grid.north(cell),
+                        grid.east(cell),
+                        grid.south(cell),
+                        grid.west(cell),
+                    ]
+                    .into_iter()
+                    .flatten()
+                    .collect()
+                }
+            }
+        )+
+    };
+}
+
+rectangular_heading!(Regular, Masked, Weighted);
+
+impl<K: Wrapping> Heading for K {
+    fn around(grid: &Grid<Self>, cell: Self::Cell) -> Vec<Self::Cell> {
+        let mut around = Vec::with_capacity(4);
+        for other in [
+            grid.north(cell),
+            grid.east(cell),
+            grid.south(cell),
+            grid.west(cell),
+        ]
+        .into_iter()
+        .flatten()
+        {
+            // narrow grids can wrap back onto the same cell or reach one neighbour two ways
+            if other != cell && !around.contains(&other) {
+                around.push(other);
+            }
+        }
+
+        around
+    }
+}
+
+impl Heading for Cube {
+    fn around(grid: &Grid<Self>, cell: Self::Cell) -> Vec<Self::Cell> {
+        // every face is drawn as seen from outside, so clockwise carries on over the edges
+        [
+            grid.north(cell),
+            grid.east(cell),
+            grid.south(cell),
+            grid.west(cell),
+        ]
+        .into_iter()
+        .flatten()
+        .collect()
+    }
+}
+
+impl Heading for Polar {
+    fn around(grid: &Grid<Self>, cell: Self::Cell) -> Vec<Self::Cell> {
+        // columns run clockwise, so the outer edge is walked in column order
+        let row_len = grid.row_len(cell.row) as isize;
+        let counter_clockwise = grid
+            .get(cell.row, (cell.col - 1).rem_euclid(row_len))
+            .filter(|other| Some(*other) != grid.clockwise(cell));
+
+        grid.outward(cell)
+            .chain(grid.clockwise(cell))
+            .chain(grid.inward(cell))
+            .chain(counter_clockwise)
+            .filter(|other| *other != cell)
+            .collect()
+    }
+}
+
+impl Heading for Hex {
+    fn around(grid: &Grid<Self>, cell: Self::Cell) -> Vec<Self::Cell> {
+        [
+            grid.north(cell),
+            grid.north_east(cell),
+            grid.south_east(cell),
+            grid.south(cell),
+            grid.south_west(cell),
+            grid.north_west(cell),
+        ]
+        .into_iter()
+        .flatten()
+        .collect()
+    }
+}
+
+impl Heading for Upsilon {
+    fn around(grid: &Grid<Self>, cell: Self::Cell) -> Vec<Self::Cell> {
+        [
+            grid.north(cell),
+            grid.north_east(cell),
+            grid.east(cell),
+            grid.south_east(cell),
+            grid.south(cell),
+            grid.south_west(cell),
+            grid.west(cell),
+            grid.north_west(cell),
+        ]
+        .into_iter()
+        .flatten()
+        .collect()
+    }
+}
+
+impl Heading for Triangle {
+    fn around(grid: &Grid<Self>, cell: Self::Cell) -> Vec<Self::Cell> {
+        let edges = if cell.is_upright() {
+            [grid.east(cell), grid.south(cell), grid.west(cell)]
+        } else {
+            [grid.north(cell), grid.east(cell), grid.west(cell)]
+        };
+
+        edges.into_iter().flatten().collect()
+    }
 }
 
 macro_rules! default_prepare_grid {
@@ -180,6 +504,26 @@ impl Kind for Regular {
     default_neighbouring_cells!();
     default_prepare_grid!();
     default_neighbours!();
+
+    fn generators() -> Vec<Box<dyn Generator<Self>>> {
+        let mut generators = generators::generic();
+        generators.extend([
+            Box::new(BinaryTree) as Box<dyn Generator<Self>>,
+            Box::new(Sidewinder),
+            Box::new(Ellers),
+            Box::new(RecursiveDivision),
+        ]);
+        generators
+    }
+}
+
+impl Rectangular for Regular {
+    fn num_cols(&self) -> usize {
+        self.cols
+    }
+    fn cell_at(&self, row: isize, col: isize, _level: isize) -> Option<RegularCell> {
+        Some(RegularCell::new(row, col))
+    }
 }
 
 impl Kind for Masked {
@@ -211,6 +555,21 @@ impl Kind for Masked {
 
     default_neighbouring_cells!();
     default_neighbours!();
+
+    fn generators() -> Vec<Box<dyn Generator<Self>>> {
+        let mut generators = generators::generic();
+        generators.push(Box::new(Ellers));
+        generators
+    }
+}
+
+impl Rectangular for Masked {
+    fn num_cols(&self) -> usize {
+        self.0.num_cols()
+    }
+    fn cell_at(&self, row: isize, col: isize, _level: isize) -> Option<RegularCell> {
+        self.0[row as usize][col as usize].then(|| RegularCell::new(row, col))
+    }
 }
 
 impl Kind for Polar {
@@ -227,15 +586,16 @@ impl Kind for Polar {
         let mut links = UnGraphMap::new();
         links.add_node(PolarCell { row: 0, col: 0 });
 
+        let mut previous_count = 1;
         for row in 1..self.rows {
             let radius = row as f32 / rows;
             let circumference = 2.0 * f32::consts::PI * radius;
 
-            let previous_count = row_len(&links, row as isize - 1);
             let estimated_cell_width = circumference / previous_count as f32;
             let ratio = (estimated_cell_width / row_height).round() as usize;
 
             let cells = previous_count * ratio;
+            previous_count = cells;
             for col in 0..cells {
                 links.add_node(PolarCell {
                     row: row as isize,
@@ -271,6 +631,25 @@ impl Kind for Polar {
             .flatten()
             .chain(grid.outward(cell))
     }
+
+    fn heuristic(&self, cell: Self::Cell, goal: Self::Cell) -> usize {
+        // columns don't line up between rows, so only moving in or out is certain
+        cell.row.abs_diff(goal.row)
+    }
+
+    fn direction(grid: &Grid<Self>, from: Self::Cell, to: Self::Cell) -> Option<Direction> {
+        if grid.inward(from) == Some(to) {
+            Some(Direction::Inward)
+        } else if grid.outward(from).any(|outward| outward == to) {
+            Some(Direction::Outward)
+        } else if grid.clockwise(from) == Some(to) {
+            Some(Direction::Clockwise)
+        } else if grid.clockwise(to) == Some(from) {
+            Some(Direction::CounterClockwise)
+        } else {
+            None
+        }
+    }
 }
 
 impl Kind for Hex {
@@ -308,6 +687,41 @@ impl Kind for Hex {
             .into_iter()
             .flatten()
     }
+
+    fn generators() -> Vec<Box<dyn Generator<Self>>> {
+        let mut generators = generators::generic();
+        generators.extend([
+            Box::new(BinaryTree) as Box<dyn Generator<Self>>,
+            Box::new(Sidewinder),
+            Box::new(Ellers),
+        ]);
+        generators
+    }
+
+    fn heuristic(&self, cell: Self::Cell, goal: Self::Cell) -> usize {
+        // diagonal moves change the row and column together, so count steps in cube coordinates
+        let cube = |cell: HexCell| {
+            let x = cell.col;
+            let z = cell.row - (cell.col - cell.col.rem_euclid(2)) / 2;
+            (x, z, -x - z)
+        };
+        let (x1, z1, y1) = cube(cell);
+        let (x2, z2, y2) = cube(goal);
+
+        x1.abs_diff(x2).max(y1.abs_diff(y2)).max(z1.abs_diff(z2))
+    }
+
+    fn direction(_grid: &Grid<Self>, from: Self::Cell, to: Self::Cell) -> Option<Direction> {
+        match (to.col - from.col, to.row) {
+            (0, row) if row == from.row - 1 => Some(Direction::North),
+            (0, row) if row == from.row + 1 => Some(Direction::South),
+            (1, row) if row == from.north_diagonal_row() => Some(Direction::NorthEast),
+            (1, row) if row == from.south_diagonal_row() => Some(Direction::SouthEast),
+            (-1, row) if row == from.north_diagonal_row() => Some(Direction::NorthWest),
+            (-1, row) if row == from.south_diagonal_row() => Some(Direction::SouthWest),
+            _ => None,
+        }
+    }
 }
 
 impl Kind for Triangle {
@@ -320,6 +734,79 @@ impl Kind for Triangle {
     default_prepare_grid!();
     default_neighbouring_cells!();
     default_neighbours!();
+
+    // only one of the cells above and below shares a side, so go by the neighbours rather than
+    // the offset, or a link between the tips of two triangles would pass as valid
+    fn direction(grid: &Grid<Self>, from: Self::Cell, to: Self::Cell) -> Option<Direction> {
+        [
+            (grid.north(from), Direction::North),
+            (grid.south(from), Direction::South),
+            (grid.east(from), Direction::East),
+            (grid.west(from), Direction::West),
+        ]
+        .into_iter()
+        .find_map(|(cell, direction)| (cell == Some(to)).then_some(direction))
+    }
+
+    fn is_straight(
+        _grid: &Grid<Self>,
+        _cell: Self::Cell,
+        _from: Self::Cell,
+        _to: Self::Cell,
+    ) -> bool {
+        // any two sides of a triangle meet at an angle, so every way through one turns
+        false
+    }
+}
+
+impl Kind for Upsilon {
+    type Cell = UpsilonCell;
+
+    fn num_rows(&self) -> usize {
+        self.rows
+    }
+
+    default_prepare_grid!();
+
+    fn neighbouring_cells(grid: &Grid<Self>) -> Vec<(Self::Cell, Self::Cell)> {
+        grid.cells()
+            .into_iter()
+            .flat_map(|cell| {
+                [
+                    Some(cell).zip(grid.south(cell)),
+                    Some(cell).zip(grid.east(cell)),
+                    Some(cell).zip(grid.south_east(cell)),
+                    Some(cell).zip(grid.south_west(cell)),
+                ]
+                .into_iter()
+                .flatten()
+            })
+            .collect()
+    }
+
+    fn neighbours(&self, grid: &Grid<Self>, cell: Self::Cell) -> impl Iterator<Item = Self::Cell> {
+        Self::around(grid, cell).into_iter()
+    }
+
+    fn heuristic(&self, cell: Self::Cell, goal: Self::Cell) -> usize {
+        // octagons step diagonally, moving a row and a column at once
+        cell.row.abs_diff(goal.row).max(cell.col.abs_diff(goal.col))
+    }
+
+    fn direction(grid: &Grid<Self>, from: Self::Cell, to: Self::Cell) -> Option<Direction> {
+        [
+            (grid.north(from), Direction::North),
+            (grid.north_east(from), Direction::NorthEast),
+            (grid.east(from), Direction::East),
+            (grid.south_east(from), Direction::SouthEast),
+            (grid.south(from), Direction::South),
+            (grid.south_west(from), Direction::SouthWest),
+            (grid.west(from), Direction::West),
+            (grid.north_west(from), Direction::NorthWest),
+        ]
+        .into_iter()
+        .find_map(|(cell, direction)| (cell == Some(to)).then_some(direction))
+    }
 }
 
 impl Kind for Weighted {
@@ -332,6 +819,21 @@ impl Kind for Weighted {
     default_prepare_grid!();
     default_neighbouring_cells!();
     default_neighbours!();
+
+    fn generators() -> Vec<Box<dyn Generator<Self>>> {
+        let mut generators = generators::generic();
+        generators.push(Box::new(Ellers));
+        generators
+    }
+}
+
+impl Rectangular for Weighted {
+    fn num_cols(&self) -> usize {
+        self.cols
+    }
+    fn cell_at(&self, row: isize, col: isize, _level: isize) -> Option<WeightedCell> {
+        Some(WeightedCell::new(row, col))
+    }
 }
 
 impl Kind for Weave {
@@ -345,17 +847,7 @@ impl Kind for Weave {
     default_neighbouring_cells!();
 
     fn link(grid: &mut Grid<Self>, cell: Self::Cell, other: Self::Cell) {
-        let neighbour = if grid.north(cell).is_some() && grid.north(cell) == grid.south(other) {
-            grid.north(cell)
-        } else if grid.south(cell).is_some() && grid.south(cell) == grid.north(other) {
-            grid.south(cell)
-        } else if grid.east(cell).is_some() && grid.east(cell) == grid.west(other) {
-            grid.east(cell)
-        } else if grid.west(cell).is_some() && grid.west(cell) == grid.east(other) {
-            grid.west(cell)
-        } else {
-            None
-        };
+        let neighbour = Self::crossing(grid, cell, other);
 
         match neighbour {
             Some(WeaveCell::Over(neighbour)) => {
@@ -368,6 +860,20 @@ impl Kind for Weave {
         }
     }
 
+    fn crossing(grid: &Grid<Self>, cell: Self::Cell, other: Self::Cell) -> Option<Self::Cell> {
+        if grid.north(cell).is_some() && grid.north(cell) == grid.south(other) {
+            grid.north(cell)
+        } else if grid.south(cell).is_some() && grid.south(cell) == grid.north(other) {
+            grid.south(cell)
+        } else if grid.east(cell).is_some() && grid.east(cell) == grid.west(other) {
+            grid.east(cell)
+        } else if grid.west(cell).is_some() && grid.west(cell) == grid.east(other) {
+            grid.west(cell)
+        } else {
+            None
+        }
+    }
+
     fn neighbours(&self, grid: &Grid<Self>, cell: WeaveCell) -> impl Iterator<Item = WeaveCell> {
         let north = grid.north(cell);
         let south = grid.south(cell);
@@ -399,6 +905,40 @@ impl Kind for Weave {
 
         neighbours.into_iter().flatten()
     }
+
+    fn generators() -> Vec<Box<dyn Generator<Self>>> {
+        let mut generators = generators::generic();
+        generators.push(Box::new(KruskalsBetterWeave));
+        generators
+    }
+
+    fn is_valid_link(grid: &Grid<Self>, cell: Self::Cell, other: Self::Cell) -> bool {
+        let (under, over) = match (cell, other) {
+            (WeaveCell::Over(_), WeaveCell::Over(_)) => {
+                return Self::direction(grid, cell, other).is_some()
+            }
+            (WeaveCell::Under(_), WeaveCell::Under(_)) => return false,
+            (WeaveCell::Under(_), WeaveCell::Over(_)) => (cell, other),
+            (WeaveCell::Over(_), WeaveCell::Under(_)) => (other, cell),
+        };
+
+        // an under cell carries a straight passage beneath an over cell running across it
+        let Some(direction) = Self::direction(grid, under, over) else {
+            return false;
+        };
+        let crossing = grid.get(under.row(), under.col());
+        match direction {
+            Direction::North | Direction::South => {
+                !grid.is_horizontal_passage(under)
+                    && crossing.is_some_and(|crossing| grid.is_horizontal_passage(crossing))
+            }
+            Direction::East | Direction::West => {
+                !grid.is_vertical_passage(under)
+                    && crossing.is_some_and(|crossing| grid.is_vertical_passage(crossing))
+            }
+            _ => false,
+        }
+    }
 }
 
 impl Kind for ThreeD {
@@ -450,4 +990,326 @@ impl Kind for ThreeD {
 
         [north, south, west, east, up, down].into_iter().flatten()
     }
+
+    fn generators() -> Vec<Box<dyn Generator<Self>>> {
+        let mut generators = generators::generic();
+        generators.extend([
+            Box::new(BinaryTree) as Box<dyn Generator<Self>>,
+            Box::new(Sidewinder),
+            Box::new(Ellers),
+        ]);
+        generators
+    }
+}
+
+impl Rectangular for ThreeD {
+    fn num_cols(&self) -> usize {
+        self.cols
+    }
+    fn num_levels(&self) -> usize {
+        self.levels
+    }
+    fn cell_at(&self, row: isize, col: isize, level: isize) -> Option<ThreeDCell> {
+        Some(ThreeDCell::new(row, col, level))
+    }
+}
+
+impl Kind for Cube {
+    type Cell = CubeCell;
+
+    fn num_rows(&self) -> usize {
+        self.size
+    }
+
+    fn prepare_grid(&self) -> UnGraphMap<CubeCell, ()> {
+        let size = self.size as isize;
+
+        let mut links = UnGraphMap::with_capacity(6 * self.size * self.size, 0);
+        for face in 0..6 {
+            for row in 0..size {
+                for col in 0..size {
+                    links.add_node(CubeCell::new(face, row, col));
+                }
+            }
+        }
+
+        links
+    }
+
+    fn neighbouring_cells(grid: &Grid<Self>) -> Vec<(Self::Cell, Self::Cell)> {
+        // which way round a pair is depends on how the faces meet, so take each from its lesser
+        // cell rather than only looking south and east
+        grid.cells()
+            .into_iter()
+            .flat_map(|cell| {
+                Self::around(grid, cell)
+                    .into_iter()
+                    .filter(move |other| cell < *other)
+                    .map(move |other| (cell, other))
+            })
+            .collect()
+    }
+
+    fn neighbours(&self, grid: &Grid<Self>, cell: Self::Cell) -> impl Iterator<Item = Self::Cell> {
+        Self::around(grid, cell).into_iter()
+    }
+
+    /// Half the Manhattan distance between the centres in space, as each step moves a cell along
+    /// a face or half a cell along each of two faces over an edge.
+    fn heuristic(&self, cell: Self::Cell, goal: Self::Cell) -> usize {
+        let from = cell.position(self.size);
+        let to = goal.position(self.size);
+
+        (0..3)
+            .map(|axis| from[axis].abs_diff(to[axis]))
+            .sum::<usize>()
+            / 2
+    }
+
+    fn direction(grid: &Grid<Self>, from: Self::Cell, to: Self::Cell) -> Option<Direction> {
+        [
+            (grid.north(from), Direction::North),
+            (grid.south(from), Direction::South),
+            (grid.east(from), Direction::East),
+            (grid.west(from), Direction::West),
+        ]
+        .into_iter()
+        .find_map(|(cell, direction)| (cell == Some(to)).then_some(direction))
+    }
+}
+
+impl Wrapping for Cylinder {
+    const ACROSS: Join = Join::Straight;
+    const DOWN: Join = Join::Open;
+
+    fn num_cols(&self) -> usize {
+        self.cols
+    }
+}
+
+impl Wrapping for Torus {
+    const ACROSS: Join = Join::Straight;
+    const DOWN: Join = Join::Straight;
+
+    fn num_cols(&self) -> usize {
+        self.cols
+    }
+}
+
+impl Wrapping for MobiusStrip {
+    const ACROSS: Join = Join::Twisted;
+    const DOWN: Join = Join::Open;
+
+    fn num_cols(&self) -> usize {
+        self.cols
+    }
+}
+
+impl Wrapping for KleinBottle {
+    const ACROSS: Join = Join::Twisted;
+    const DOWN: Join = Join::Straight;
+
+    fn num_cols(&self) -> usize {
+        self.cols
+    }
+}
+
+macro_rules! wrapping_kind {
+    ($($T:ty),+ $(,)?) => {
+        $(
+            impl Kind for $T {
+                type Cell = RegularCell;
+
+                fn num_rows(&self) -> usize {
+                    self.rows
+                }
+
+                default_prepare_grid!();
+
+                fn neighbouring_cells(grid: &Grid<Self>) -> Vec<(Self::Cell, Self::Cell)> {
+                    let mut pairs = grid
+                        .cells()
+                        .into_iter()
+                        .flat_map(|cell| {
+                            [grid.south(cell), grid.east(cell)]
+                                .into_iter()
+                                .flatten()
+                                .filter(move |other| *other != cell)
+                                .map(move |other| (cell.min(other), cell.max(other)))
+                        })
+                        .collect::<Vec<_>>();
+                    pairs.sort_unstable();
+                    pairs.dedup();
+                    pairs
+                }
+
+                fn neighbours(
+                    &self,
+                    grid: &Grid<Self>,
+                    cell: Self::Cell,
+                ) -> impl Iterator<Item = Self::Cell> {
+                    Self::around(grid, cell).into_iter()
+                }
+
+                /// The Manhattan distance to the nearest copy of `goal` on the grids joined
+                /// around this one, which can be closer the other way around the surface.
+                fn heuristic(&self, cell: Self::Cell, goal: Self::Cell) -> usize {
+                    let (rows, cols) = (self.rows as isize, self.cols as isize);
+                    let shifts = |join| match join {
+                        Join::Open => &[0][..],
+                        Join::Straight | Join::Twisted => &[-1, 0, 1][..],
+                    };
+
+                    let mut nearest = usize::MAX;
+                    for across in shifts(Self::ACROSS) {
+                        for down in shifts(Self::DOWN) {
+                            let (mut row, mut col) = (goal.row, goal.col);
+                            if *across != 0 && Self::ACROSS == Join::Twisted {
+                                row = rows - 1 - row;
+                            }
+                            if *down != 0 && Self::DOWN == Join::Twisted {
+                                col = cols - 1 - col;
+                            }
+                            let distance = cell.row.abs_diff(row + down * rows)
+                                + cell.col.abs_diff(col + across * cols);
+                            nearest = nearest.min(distance);
+                        }
+                    }
+
+                    nearest
+                }
+
+                fn direction(grid: &Grid<Self>, from: Self::Cell, to: Self::Cell) -> Option<Direction> {
+                    [
+                        (grid.north(from), Direction::North),
+                        (grid.south(from), Direction::South),
+                        (grid.east(from), Direction::East),
+                        (grid.west(from), Direction::West),
+                    ]
+                    .into_iter()
+                    .find_map(|(cell, direction)| (cell == Some(to)).then_some(direction))
+                }
+            }
+        )+
+    };
+}
+
+wrapping_kind!(Cylinder, Torus, MobiusStrip, KleinBottle);
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use super::*;
+    use crate::generators::Registry;
+
+    #[test]
+    fn weave_generators_make_valid_crossings() {
+        for generator in Registry::<Weave>::new().iter() {
+            for seed in 0..8 {
+                let grid = Grid::new(Weave::new(8, 8), None, None)
+                    .generate(generator, &mut StdRng::seed_from_u64(seed));
+                assert!(grid.invalid_links().is_empty(), "{}", generator.name());
+                assert!(grid.is_perfect(), "{}", generator.name());
+            }
+        }
+    }
+
+    fn wrapping_neighbours_are_symmetric<K: Wrapping>(kind: K) {
+        let grid = Grid::new(kind, None, None);
+
+        for cell in grid.cells() {
+            // stepping back the opposite way returns, even across a twisted edge
+            let back = [
+                grid.north(cell).and_then(|other| grid.south(other)),
+                grid.south(cell).and_then(|other| grid.north(other)),
+                grid.east(cell).and_then(|other| grid.west(other)),
+                grid.west(cell).and_then(|other| grid.east(other)),
+            ];
+            assert!(
+                back.into_iter().flatten().all(|other| other == cell),
+                "{cell:?}"
+            );
+
+            for other in grid.neighbours(cell) {
+                assert_ne!(other, cell);
+                assert!(grid.neighbours(other).any(|back| back == cell), "{cell:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn wrapped_edges_join_both_ways() {
+        for (rows, cols) in [(4, 5), (5, 1), (1, 4), (2, 2)] {
+            wrapping_neighbours_are_symmetric(Cylinder::new(rows, cols));
+            wrapping_neighbours_are_symmetric(Torus::new(rows, cols));
+            wrapping_neighbours_are_symmetric(MobiusStrip::new(rows, cols));
+            wrapping_neighbours_are_symmetric(KleinBottle::new(rows, cols));
+        }
+    }
+
+    fn perfect<K: Kind + Clone>(kind: &K) {
+        for generator in Registry::<K>::new().iter() {
+            for seed in 0..4 {
+                let grid = Grid::new(kind.clone(), None, None)
+                    .generate(generator, &mut StdRng::seed_from_u64(seed));
+                assert!(grid.is_perfect(), "{} with seed {seed}", generator.name());
+            }
+        }
+    }
+
+    #[test]
+    fn wrapping_generators_make_perfect_mazes() {
+        for (rows, cols) in [(5, 6), (4, 1), (1, 4)] {
+            perfect(&Cylinder::new(rows, cols));
+            perfect(&Torus::new(rows, cols));
+            perfect(&MobiusStrip::new(rows, cols));
+            perfect(&KleinBottle::new(rows, cols));
+        }
+    }
+
+    #[test]
+    fn cube_generators_make_perfect_mazes() {
+        perfect(&Cube::new(1));
+        perfect(&Cube::new(3));
+    }
+
+    #[test]
+    fn upsilon_squares_meet_four_cells_and_octagons_eight() {
+        let (rows, cols) = (5, 6);
+        let grid = Grid::new(Upsilon::new(rows, cols), None, None);
+        let inside = |cell: UpsilonCell| {
+            (1..rows as isize - 1).contains(&cell.row) && (1..cols as isize - 1).contains(&cell.col)
+        };
+
+        for cell in grid.cells() {
+            let neighbours = grid.neighbours(cell).collect::<Vec<_>>();
+            let expected = if cell.is_octagon() { 8 } else { 4 };
+            if inside(cell) {
+                assert_eq!(neighbours.len(), expected, "{cell:?}");
+            }
+            assert!(neighbours.len() <= expected, "{cell:?}");
+
+            for other in neighbours {
+                let there = Upsilon::direction(&grid, cell, other).expect("a neighbour");
+                let back = Upsilon::direction(&grid, other, cell);
+                assert_eq!(back, Some(there.opposite()), "{cell:?} to {other:?}");
+                if !cell.is_octagon() || !other.is_octagon() {
+                    assert!(!matches!(
+                        there,
+                        Direction::NorthEast
+                            | Direction::NorthWest
+                            | Direction::SouthEast
+                            | Direction::SouthWest
+                    ));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn upsilon_generators_make_perfect_mazes() {
+        perfect(&Upsilon::new(5, 6));
+        perfect(&Upsilon::new(1, 3));
+    }
 }
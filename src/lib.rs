@@ -8,12 +8,34 @@
     clippy::explicit_iter_loop
 )]
 
+mod animation;
+mod canvas;
 pub mod cell;
+mod chunks;
+mod connectivity;
 mod distances;
-mod generators;
+mod error;
+pub mod generators;
 mod grid;
+mod index;
 pub mod kind;
+mod links;
 mod mask;
+mod parse;
+mod path;
+#[cfg(feature = "serde")]
+mod persist;
+pub mod solvers;
+mod stats;
+mod style;
 
-pub use grid::Grid;
+pub use chunks::{Chunk, Chunks};
+pub use distances::Distances;
+pub use error::MazeError;
+pub use grid::{Grid, Render, Shading};
 pub use mask::Mask;
+pub use path::{Direction, Path, Step};
+#[cfg(feature = "serde")]
+pub use persist::Persist;
+pub use stats::MazeStats;
+pub use style::{Gradient, RenderOptions};
@@ -0,0 +1,308 @@
+use fixedbitset::FixedBitSet;
+use petgraph::prelude::UnGraphMap;
+use rustc_hash::FxHashMap;
+
+use crate::{
+    cell::CellKind,
+    kind::{Kind, Rectangular},
+};
+
+/// Storage for the passages between cells.
+#[derive(Clone)]
+pub(crate) enum Links<K: Kind> {
+    Graph(UnGraphMap<K::Cell, ()>),
+    Packed(PackedLinks<K>),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Wall {
+    East,
+    South,
+    Up,
+}
+
+/// Passages of a rectangular grid packed into one bit per east, south and upward wall of each
+/// position. Cells aren't stored at all but made from their position by the kind, apart from
+/// the few that differ from a fresh grid's, such as weighted ones, so a cell costs three bits.
+#[derive(Clone)]
+pub(crate) struct PackedLinks<K: Kind> {
+    rows: usize,
+    cols: usize,
+    levels: usize,
+    east: FixedBitSet,
+    south: FixedBitSet,
+    up: FixedBitSet,
+    cell_at: fn(&K, isize, isize, isize) -> Option<K::Cell>,
+    replaced: FxHashMap<usize, K::Cell>,
+    /// How many cells come before each row, to find the nth cell without walking the others.
+    row_starts: Vec<usize>,
+    len: usize,
+}
+
+impl<K: Kind> PackedLinks<K> {
+    pub(crate) fn new(kind: &K) -> Self
+    where
+        K: Rectangular,
+    {
+        let (rows, cols, levels) = (kind.num_rows(), kind.num_cols(), kind.num_levels());
+        let positions = rows * cols * levels;
+
+        let mut row_starts = Vec::with_capacity(rows);
+        let mut len = 0;
+        for row in 0..rows as isize {
+            row_starts.push(len);
+            for col in 0..cols as isize {
+                len += (0..levels as isize)
+                    .filter(|level| kind.cell_at(row, col, *level).is_some())
+                    .count();
+            }
+        }
+
+        Self {
+            rows,
+            cols,
+            levels,
+            east: FixedBitSet::with_capacity(positions),
+            south: FixedBitSet::with_capacity(positions),
+            up: FixedBitSet::with_capacity(positions),
+            cell_at: K::cell_at,
+            replaced: FxHashMap::default(),
+            row_starts,
+            len,
+        }
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.len
+    }
+
+    /// The cell at `index` in the order of [`PackedLinks::cells`].
+    pub(crate) fn nth(&self, kind: &K, index: usize) -> Option<K::Cell> {
+        if index >= self.len {
+            return None;
+        }
+        let row = self.row_starts.partition_point(|start| *start <= index) - 1;
+
+        self.row(kind, row as isize)
+            .nth(index - self.row_starts[row])
+    }
+
+    pub(crate) fn cell_at(
+        &self,
+        kind: &K,
+        row: isize,
+        col: isize,
+        level: isize,
+    ) -> Option<K::Cell> {
+        let bit = self.bit(row, col, level)?;
+        match self.replaced.get(&bit) {
+            Some(cell) => Some(*cell),
+            None => (self.cell_at)(kind, row, col, level),
+        }
+    }
+
+    /// Cells in the same order as the graph a fresh grid of the kind starts with, so that
+    /// generators make the same maze from the same seed whichever storage they carve.
+    pub(crate) fn cells<'a>(&'a self, kind: &'a K) -> impl Iterator<Item = K::Cell> + 'a {
+        (0..self.rows as isize).flat_map(move |row| self.row(kind, row))
+    }
+
+    fn row<'a>(&'a self, kind: &'a K, row: isize) -> impl Iterator<Item = K::Cell> + 'a {
+        (0..self.cols as isize).flat_map(move |col| {
+            (0..self.levels as isize).filter_map(move |level| self.cell_at(kind, row, col, level))
+        })
+    }
+
+    /// Stores `cell` in place of the one its kind makes at that position.
+    pub(crate) fn replace(&mut self, cell: K::Cell) {
+        if let Some(bit) = self.bit(cell.row(), cell.col(), cell.level()) {
+            self.replaced.insert(bit, cell);
+        }
+    }
+
+    pub(crate) fn insert(&mut self, cell: impl CellKind, other: impl CellKind) {
+        let (wall, bit) = self
+            .wall_between(cell, other)
+            .expect("packed grids can only link adjacent cells");
+        self.bits_mut(wall).insert(bit);
+    }
+
+    pub(crate) fn remove(&mut self, cell: impl CellKind, other: impl CellKind) {
+        if let Some((wall, bit)) = self.wall_between(cell, other) {
+            self.bits_mut(wall).set(bit, false);
+        }
+    }
+
+    pub(crate) fn contains(&self, cell: impl CellKind, other: impl CellKind) -> bool {
+        self.wall_between(cell, other)
+            .is_some_and(|(wall, bit)| self.bits(wall)[bit])
+    }
+
+    /// Positions as `(row, col, level)` that `cell` has a passage to.
+    pub(crate) fn neighbours(
+        &self,
+        cell: impl CellKind,
+    ) -> impl Iterator<Item = (isize, isize, isize)> {
+        let (row, col, level) = (cell.row(), cell.col(), cell.level());
+        let is_open = |wall, row, col, level| {
+            self.bit(row, col, level)
+                .is_some_and(|bit| self.bits(wall)[bit])
+        };
+
+        [
+            is_open(Wall::South, row - 1, col, level).then_some((row - 1, col, level)),
+            is_open(Wall::South, row, col, level).then_some((row + 1, col, level)),
+            is_open(Wall::East, row, col - 1, level).then_some((row, col - 1, level)),
+            is_open(Wall::East, row, col, level).then_some((row, col + 1, level)),
+            is_open(Wall::Up, row, col, level - 1).then_some((row, col, level - 1)),
+            is_open(Wall::Up, row, col, level).then_some((row, col, level + 1)),
+        ]
+        .into_iter()
+        .flatten()
+    }
+
+    fn wall_between(&self, cell: impl CellKind, other: impl CellKind) -> Option<(Wall, usize)> {
+        let cell = (cell.level(), cell.row(), cell.col());
+        let other = (other.level(), other.row(), other.col());
+        let (lower, upper) = if cell < other {
+            (cell, other)
+        } else {
+            (other, cell)
+        };
+
+        let wall = match (upper.0 - lower.0, upper.1 - lower.1, upper.2 - lower.2) {
+            (0, 0, 1) => Wall::East,
+            (0, 1, 0) => Wall::South,
+            (1, 0, 0) => Wall::Up,
+            _ => return None,
+        };
+        let (level, row, col) = lower;
+        let bit = self.bit(row, col, level)?;
+
+        Some((wall, bit))
+    }
+
+    fn bit(&self, row: isize, col: isize, level: isize) -> Option<usize> {
+        let row = usize::try_from(row).ok().filter(|row| *row < self.rows)?;
+        let col = usize::try_from(col).ok().filter(|col| *col < self.cols)?;
+        let level = usize::try_from(level)
+            .ok()
+            .filter(|level| *level < self.levels)?;
+
+        Some((level * self.rows + row) * self.cols + col)
+    }
+
+    fn bits(&self, wall: Wall) -> &FixedBitSet {
+        match wall {
+            Wall::East => &self.east,
+            Wall::South => &self.south,
+            Wall::Up => &self.up,
+        }
+    }
+
+    fn bits_mut(&mut self, wall: Wall) -> &mut FixedBitSet {
+        match wall {
+            Wall::East => &mut self.east,
+            Wall::South => &mut self.south,
+            Wall::Up => &mut self.up,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, SeedableRng};
+    use rustc_hash::FxHashSet;
+
+    use crate::{
+        generators::Registry,
+        grid::Grid,
+        kind::{Masked, Regular, ThreeD, Weighted},
+        mask::Mask,
+    };
+
+    use super::*;
+
+    fn links<K: Kind>(grid: &Grid<K>) -> FxHashSet<(K::Cell, K::Cell)> {
+        grid.cells()
+            .into_iter()
+            .flat_map(|cell| grid.links(cell).map(move |other| (cell, other)))
+            .collect()
+    }
+
+    fn same_mazes<K: Rectangular + Clone>(kind: &K) {
+        for generator in Registry::<K>::new().iter() {
+            for seed in 0..3 {
+                let graph = Grid::new(kind.clone(), None, None)
+                    .generate(generator, &mut StdRng::seed_from_u64(seed));
+                let packed = Grid::new_packed(kind.clone(), None, None)
+                    .generate(generator, &mut StdRng::seed_from_u64(seed));
+
+                assert_eq!(packed.cells(), graph.cells(), "{}", generator.name());
+                assert_eq!(links(&packed), links(&graph), "{}", generator.name());
+            }
+        }
+    }
+
+    #[test]
+    fn packed_grids_carve_the_same_mazes_as_graphs() {
+        let mut mask = Mask::new(6, 7);
+        mask[0][0] = false;
+        mask[3][2] = false;
+        mask[3][3] = false;
+
+        same_mazes(&Regular::new(6, 7));
+        same_mazes(&Masked::new(mask));
+        same_mazes(&Weighted::new(5, 5));
+        same_mazes(&ThreeD::new(3, 4, 3));
+    }
+
+    #[test]
+    fn packed_weights_replace_cells_in_place() {
+        let mut grid = Grid::new_packed(Weighted::new(3, 3), None, None);
+        grid.set_weight(1, 2, 7);
+
+        assert_eq!(grid.get(1, 2).map(|cell| cell.weight()), Some(7));
+        assert_eq!(grid.size(), 9);
+        assert_eq!(
+            grid.cells().iter().map(|cell| cell.weight()).sum::<usize>(),
+            15
+        );
+    }
+
+    #[test]
+    fn weights_keep_the_order_of_cells() {
+        let positions = |grid: &Grid<Weighted>| {
+            grid.cells()
+                .iter()
+                .map(|cell| (cell.row(), cell.col()))
+                .collect::<Vec<_>>()
+        };
+        let mut graph = Grid::new(Weighted::new(4, 4), None, None);
+        let mut packed = Grid::new_packed(Weighted::new(4, 4), None, None);
+        let before = positions(&graph);
+        for (row, col, weight) in [(0, 0, 3), (2, 1, 5), (3, 3, 9), (2, 1, 2)] {
+            graph.set_weight(row, col, weight);
+            packed.set_weight(row, col, weight);
+        }
+
+        assert_eq!(positions(&graph), before);
+        assert_eq!(graph.cells(), packed.cells());
+        for seed in 0..16 {
+            assert_eq!(
+                graph.get_random_cell_with_rng(&mut StdRng::seed_from_u64(seed)),
+                packed.get_random_cell_with_rng(&mut StdRng::seed_from_u64(seed))
+            );
+        }
+        for generator in Registry::<Weighted>::new().iter() {
+            let graph = graph
+                .clone()
+                .generate(generator, &mut StdRng::seed_from_u64(1));
+            let packed = packed
+                .clone()
+                .generate(generator, &mut StdRng::seed_from_u64(1));
+
+            assert_eq!(links(&packed), links(&graph), "{}", generator.name());
+        }
+    }
+}
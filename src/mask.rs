@@ -1,7 +1,11 @@
-use std::{fs, ops, path::Path};
+use core::fmt;
+use std::{fs, ops, path::Path, str::FromStr};
 
 use image::Rgb;
 
+use crate::error::MazeError;
+
+#[derive(Debug, Clone)]
 pub struct Mask {
     rows: usize,
     cols: usize,
@@ -18,21 +22,22 @@ impl Mask {
     }
 
     pub fn from_txt(path: impl AsRef<Path>) -> Self {
-        let file = fs::read_to_string(path).expect("file should be found");
-        let bits: Vec<Vec<bool>> = file
-            .lines()
-            .map(|line| line.chars().map(|c| c != 'X').collect())
-            .collect();
+        Self::try_from_txt(path).expect("mask file should be readable")
+    }
 
-        Self {
-            rows: bits.len(),
-            cols: bits[0].len(),
-            bits,
-        }
+    /// Reads a mask from text where `X` marks a cell that's masked off, failing if the file
+    /// can't be read, is empty or has rows of different lengths.
+    pub fn try_from_txt(path: impl AsRef<Path>) -> Result<Self, MazeError> {
+        fs::read_to_string(path)?.parse()
     }
 
     pub fn from_image(path: impl AsRef<Path>) -> Self {
-        let image = image::open(path).expect("image should be found").to_rgb8();
+        Self::try_from_image(path).expect("mask image should be readable")
+    }
+
+    /// Reads a mask from an image where black pixels mark cells that are masked off.
+    pub fn try_from_image(path: impl AsRef<Path>) -> Result<Self, MazeError> {
+        let image = image::open(path)?.to_rgb8();
 
         let cols = image.width();
         let rows = image.height();
@@ -44,11 +49,11 @@ impl Mask {
             })
             .collect();
 
-        Self {
+        Ok(Self {
             rows: rows as usize,
             cols: cols as usize,
             bits,
-        }
+        })
     }
 
     pub fn num_rows(&self) -> usize {
@@ -59,6 +64,51 @@ impl Mask {
     }
 }
 
+impl FromStr for Mask {
+    type Err = MazeError;
+
+    /// Parses the format of [`Mask::from_txt`], one line per row.
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let bits: Vec<Vec<bool>> = text
+            .lines()
+            .map(|line| line.chars().map(|c| c != 'X').collect())
+            .collect();
+
+        let cols = bits.first().map_or(0, Vec::len);
+        if cols == 0 {
+            return Err(MazeError::EmptyMask);
+        }
+        if let Some((row, line)) = bits.iter().enumerate().find(|(_, line)| line.len() != cols) {
+            return Err(MazeError::UnevenMask {
+                row,
+                expected: cols,
+                found: line.len(),
+            });
+        }
+
+        Ok(Self {
+            rows: bits.len(),
+            cols,
+            bits,
+        })
+    }
+}
+
+impl fmt::Display for Mask {
+    /// Writes the mask in the format read by [`Mask::from_txt`], with `X` for masked off cells.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in &self.bits {
+            let line = row
+                .iter()
+                .map(|on| if *on { '.' } else { 'X' })
+                .collect::<String>();
+            writeln!(f, "{line}")?;
+        }
+
+        Ok(())
+    }
+}
+
 impl ops::Index<usize> for Mask {
     type Output = Vec<bool>;
 
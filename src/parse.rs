@@ -0,0 +1,202 @@
+use std::str::FromStr;
+
+use crate::{
+    cell::RegularCell,
+    error::MazeError,
+    grid::Grid,
+    kind::{Masked, Regular},
+    mask::Mask,
+};
+
+/// The passages of a rectangular maze drawn as text, either with the box-drawing characters
+/// of `Display` or the classic ASCII of `+---+` corners and `|` walls.
+///
+/// Only whether each wall position is blank matters, so anything written inside the cells,
+/// such as distances, is ignored. Box-drawing cells are three characters wide, while ASCII
+/// cells are as wide as the first run of `-` in the top line.
+struct Walls {
+    rows: usize,
+    cols: usize,
+    open_east: Vec<Vec<bool>>,
+    open_south: Vec<Vec<bool>>,
+}
+
+impl Walls {
+    fn parse(text: &str) -> Result<Self, MazeError> {
+        let lines = text
+            .lines()
+            .map(|line| line.chars().collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        let invalid = |reason: &str| MazeError::InvalidMaze(reason.to_string());
+
+        let Some(top) = lines.first() else {
+            return Err(invalid("no lines to read a maze from"));
+        };
+        let width = if top.iter().any(|c| matches!(c, '+' | '-')) {
+            top.iter()
+                .skip_while(|c| **c != '-')
+                .take_while(|c| **c == '-')
+                .count()
+        } else {
+            3
+        };
+
+        let line_len = lines.iter().map(Vec::len).max().unwrap_or(0);
+        if lines.len() < 3 || lines.len() % 2 == 0 {
+            return Err(invalid("expected a wall line above and below each row"));
+        }
+        if width == 0 || line_len < width + 2 || (line_len - 1) % (width + 1) != 0 {
+            return Err(invalid("lines don't split evenly into cells"));
+        }
+
+        let rows = (lines.len() - 1) / 2;
+        let cols = (line_len - 1) / (width + 1);
+        // editors tend to trim trailing blanks, so anything past the end of a line is blank
+        let is_blank = |line: usize, col: usize| lines[line].get(col).is_none_or(|c| *c == ' ');
+
+        let open_east = (0..rows)
+            .map(|row| {
+                (0..cols)
+                    .map(|col| col + 1 < cols && is_blank(2 * row + 1, (col + 1) * (width + 1)))
+                    .collect()
+            })
+            .collect();
+        let open_south = (0..rows)
+            .map(|row| {
+                (0..cols)
+                    .map(|col| {
+                        let start = col * (width + 1) + 1;
+                        row + 1 < rows && (start..start + width).all(|at| is_blank(2 * row + 2, at))
+                    })
+                    .collect()
+            })
+            .collect();
+
+        Ok(Self {
+            rows,
+            cols,
+            open_east,
+            open_south,
+        })
+    }
+
+    /// Whether any wall around the cell is open.
+    fn has_opening(&self, row: usize, col: usize) -> bool {
+        self.open_east[row][col]
+            || self.open_south[row][col]
+            || (col > 0 && self.open_east[row][col - 1])
+            || (row > 0 && self.open_south[row - 1][col])
+    }
+
+    fn passages(&self) -> impl Iterator<Item = (RegularCell, RegularCell)> + '_ {
+        (0..self.rows).flat_map(move |row| {
+            (0..self.cols).flat_map(move |col| {
+                let cell = RegularCell::new(row as isize, col as isize);
+                let east = self.open_east[row][col]
+                    .then(|| RegularCell::new(row as isize, col as isize + 1));
+                let south = self.open_south[row][col]
+                    .then(|| RegularCell::new(row as isize + 1, col as isize));
+
+                [east, south]
+                    .into_iter()
+                    .flatten()
+                    .map(move |other| (cell, other))
+            })
+        })
+    }
+}
+
+impl FromStr for Grid<Regular> {
+    type Err = MazeError;
+
+    /// Reads a maze in the format it's displayed in, or in classic `+---+` ASCII.
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let walls = Walls::parse(text)?;
+
+        let mut grid = Self::new(Regular::new(walls.rows, walls.cols), None, None);
+        for (cell, other) in walls.passages() {
+            grid.link(cell, other);
+        }
+
+        Ok(grid)
+    }
+}
+
+impl FromStr for Grid<Masked> {
+    type Err = MazeError;
+
+    /// Reads a maze like `Grid<Regular>` does, masking off the cells that are walled in on
+    /// every side. Text can't tell those apart from masked off cells, so a real cell with no
+    /// passages, as in a maze still to be carved, reads back masked off.
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let walls = Walls::parse(text)?;
+
+        let mut mask = Mask::new(walls.rows, walls.cols);
+        for row in 0..walls.rows {
+            for col in 0..walls.cols {
+                mask[row][col] = walls.has_opening(row, col);
+            }
+        }
+
+        let mut grid = Self::new(Masked::new(mask), None, None);
+        for (cell, other) in walls.passages() {
+            grid.link(cell, other);
+        }
+
+        Ok(grid)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use super::*;
+    use crate::generators::RecursiveBacktracker;
+
+    #[test]
+    fn displayed_mazes_parse_back() {
+        let grid = Grid::new(Regular::new(5, 7), None, None)
+            .generate(&RecursiveBacktracker, &mut StdRng::seed_from_u64(0));
+        let text = grid.to_string();
+        let parsed = text.parse::<Grid<Regular>>().expect("maze to parse");
+        assert_eq!(parsed.to_string(), text);
+
+        let mut mask = Mask::new(5, 7);
+        mask[0][0] = false;
+        mask[2][3] = false;
+        mask[2][4] = false;
+        let grid = Grid::new(Masked::new(mask), None, None)
+            .generate(&RecursiveBacktracker, &mut StdRng::seed_from_u64(0));
+        let text = grid.to_string();
+        let parsed = text.parse::<Grid<Masked>>().expect("masked maze to parse");
+        assert_eq!(parsed.cells(), grid.cells());
+        assert_eq!(parsed.to_string(), text);
+    }
+
+    #[test]
+    fn ascii_mazes_parse() {
+        let text = ["+--+--+", "|     |", "+--+  +", "|     |", "+--+--+"].join("\n");
+        let grid = text.parse::<Grid<Regular>>().expect("ascii maze to parse");
+        let cell = |row, col| RegularCell::new(row, col);
+
+        assert_eq!(grid.size(), 4);
+        assert!(grid.are_linked(cell(0, 0), cell(0, 1)));
+        assert!(grid.are_linked(cell(0, 1), cell(1, 1)));
+        assert!(grid.are_linked(cell(1, 0), cell(1, 1)));
+        assert!(grid.is_perfect());
+    }
+
+    #[test]
+    fn malformed_mazes_are_rejected() {
+        for text in ["", "+--+\n|  |", "+---+--\n|   |  \n+---+--"] {
+            assert!(
+                matches!(
+                    text.parse::<Grid<Regular>>(),
+                    Err(MazeError::InvalidMaze(_))
+                ),
+                "{text:?}"
+            );
+        }
+    }
+}
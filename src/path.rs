@@ -0,0 +1,187 @@
+use core::fmt;
+
+use crate::{cell::CellKind, distances::Distances, grid::Grid, kind::Kind};
+
+/// Which way a step between two neighbouring cells goes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction {
+    North,
+    NorthEast,
+    East,
+    SouthEast,
+    South,
+    SouthWest,
+    West,
+    NorthWest,
+    Up,
+    Down,
+    Inward,
+    Outward,
+    Clockwise,
+    CounterClockwise,
+}
+
+impl Direction {
+    pub fn opposite(self) -> Self {
+        match self {
+            Self::North => Self::South,
+            Self::NorthEast => Self::SouthWest,
+            Self::East => Self::West,
+            Self::SouthEast => Self::NorthWest,
+            Self::South => Self::North,
+            Self::SouthWest => Self::NorthEast,
+            Self::West => Self::East,
+            Self::NorthWest => Self::SouthEast,
+            Self::Up => Self::Down,
+            Self::Down => Self::Up,
+            Self::Inward => Self::Outward,
+            Self::Outward => Self::Inward,
+            Self::Clockwise => Self::CounterClockwise,
+            Self::CounterClockwise => Self::Clockwise,
+        }
+    }
+}
+
+/// One move along a [`Path`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Step<C> {
+    pub from: C,
+    pub to: C,
+    /// `None` if the cells aren't neighbours in the grid the path was walked in.
+    pub direction: Option<Direction>,
+}
+
+/// An ordered route through a grid, from its first cell to its last.
+pub struct Path<K: Kind> {
+    cells: Vec<K::Cell>,
+}
+
+// derives would needlessly require the kind itself to implement these
+impl<K: Kind> Clone for Path<K> {
+    fn clone(&self) -> Self {
+        Self {
+            cells: self.cells.clone(),
+        }
+    }
+}
+
+impl<K: Kind> PartialEq for Path<K> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cells == other.cells
+    }
+}
+
+impl<K: Kind> Eq for Path<K> {}
+
+impl<K: Kind> fmt::Debug for Path<K> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Path").field(&self.cells).finish()
+    }
+}
+
+// a path always has at least its start, so it can't be empty
+#[allow(clippy::len_without_is_empty)]
+impl<K: Kind> Path<K> {
+    /// A path through `cells` in order, or `None` without any, since a path needs a start.
+    pub fn new(cells: Vec<K::Cell>) -> Option<Self> {
+        (!cells.is_empty()).then_some(Self { cells })
+    }
+
+    pub fn cells(&self) -> &[K::Cell] {
+        &self.cells
+    }
+
+    pub fn start(&self) -> K::Cell {
+        self.cells[0]
+    }
+    pub fn goal(&self) -> K::Cell {
+        self.cells[self.cells.len() - 1]
+    }
+
+    /// The number of cells on the path, counting both ends.
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    /// The sum of the weights of every cell entered, the same measure as
+    /// [`Grid::distances_from`], so the start itself costs nothing.
+    pub fn cost(&self) -> usize {
+        self.cells[1..].iter().map(|cell| cell.weight()).sum()
+    }
+
+    pub fn steps<'a>(&'a self, grid: &'a Grid<K>) -> impl Iterator<Item = Step<K::Cell>> + 'a {
+        self.cells.windows(2).map(|pair| Step {
+            from: pair[0],
+            to: pair[1],
+            direction: K::direction(grid, pair[0], pair[1]),
+        })
+    }
+}
+
+impl<K: Kind> From<Path<K>> for Distances<K> {
+    /// Each cell's cost along the path, as [`Path::cost`] counts it.
+    fn from(path: Path<K>) -> Self {
+        let mut distances = Self::new(path.start());
+        let mut cost = 0;
+        for cell in path.cells.into_iter().skip(1) {
+            cost += cell.weight();
+            distances.insert(cell, cost);
+        }
+
+        distances
+    }
+}
+
+impl<K: Kind> From<Distances<K>> for Path<K> {
+    /// Orders the cells by distance, which makes sense for the breadcrumbs returned by
+    /// [`Distances::path_to`] but not for distances across a whole grid.
+    fn from(distances: Distances<K>) -> Self {
+        let mut cells = distances.cells().collect::<Vec<_>>();
+        cells.sort_by_key(|cell| distances[*cell]);
+
+        // distances always include their root
+        Self { cells }
+    }
+}
+
+impl<K: Kind> Grid<K> {
+    /// The longest of the shortest routes between any two cells, found by walking to the
+    /// furthest cell from anywhere and then to the furthest cell from there.
+    ///
+    /// This is exact for perfect mazes, but once there are loops it can come up short, so use
+    /// [`Grid::longest_path_exact`] for those if the grid is small enough.
+    pub fn longest_path(&self) -> Option<Path<K>> {
+        let any = self.cells().first().copied()?;
+        let (start, _) = self.distances_from(any).max();
+        let distances = self.distances_from(start);
+        let (goal, _) = distances.max();
+
+        distances.path_to(goal, self).map(Path::from)
+    }
+
+    /// Like [`Grid::longest_path`], but exact for any maze by measuring from every cell, which
+    /// takes time quadratic in the number of cells.
+    pub fn longest_path_exact(&self) -> Option<Path<K>> {
+        let (distances, goal, _) = self
+            .cells()
+            .into_iter()
+            .map(|cell| {
+                let distances = self.distances_from(cell);
+                let (goal, length) = distances.max();
+                (distances, goal, length)
+            })
+            .max_by_key(|(_, _, length)| *length)?;
+
+        distances.path_to(goal, self).map(Path::from)
+    }
+
+    /// Moves the start and goal to the ends of [`Grid::longest_path`].
+    pub fn with_longest_path(mut self) -> Self {
+        if let Some(path) = self.longest_path() {
+            self.set_start(path.start());
+            self.set_goal(path.goal());
+        }
+
+        self
+    }
+}
@@ -0,0 +1,431 @@
+use serde::{
+    de::{self, DeserializeOwned},
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+
+use crate::{
+    cell::{CellKind, UnderCell, WeaveCell},
+    error::MazeError,
+    grid::Grid,
+    kind::{
+        Cube, Cylinder, Hex, Kind, KleinBottle, Masked, MobiusStrip, Polar, Regular, ThreeD, Torus,
+        Triangle, Upsilon, Weave, Weighted,
+    },
+    mask::Mask,
+};
+
+const VERSION: u32 = 1;
+
+/// Kinds whose grids can be saved and loaded with serde, which the `serde` feature enables.
+///
+/// Grids are stored as a single object, which [`Grid::to_json`] writes like this for a two by
+/// two weighted grid:
+///
+/// ```json
+/// {
+///   "version": 1,
+///   "type": "weighted",
+///   "kind": { "rows": 2, "cols": 2 },
+///   "links": [[0, 0, 0, 1], [0, 0, 1, 0], [1, 0, 1, 1]],
+///   "weights": [[1, 1, 5]],
+///   "start": [0, 0],
+///   "goal": [1, 1]
+/// }
+/// ```
+///
+/// - `version` is the version of this format, currently `1`.
+/// - `type` names the kind: `regular`, `masked`, `polar`, `hex`, `triangle`, `weighted`, `weave`,
+///   `3d`, `cylinder`, `torus`, `mobius`, `klein`, `cube` or `upsilon`.
+/// - `kind` holds the kind's parameters, the same fields as its struct. A masked kind is its
+///   mask as an array of lines in the format of [`Mask::from_txt`].
+/// - Cells are written as `[row, col]`, or `[row, col, level]` in 3D grids and `[row, col, face]`
+///   on cubes. Weave cells that carry a passage underneath another are `[row, col, 1]`.
+/// - `links` has each passage once, as the positions of its two cells one after the other.
+/// - `weights` lists the weighted cells with a weight other than one, with the weight after
+///   the position. It's left out when empty.
+/// - `unders` lists the passages tunnelling beneath weave cells, each as the position of the
+///   cell on top followed by the two cells the passage links. They're not in `links`, and it's
+///   left out when empty.
+/// - `start` and `goal` are left out when unset.
+///
+/// Grids are always loaded with their passages in a graph, whichever way they were stored when
+/// saved, and loading fails on passages between cells that aren't neighbours.
+pub trait Persist: Kind + Serialize + DeserializeOwned {
+    const NAME: &'static str;
+
+    fn position(cell: Self::Cell) -> Vec<isize> {
+        vec![cell.row(), cell.col()]
+    }
+
+    fn find(grid: &Grid<Self>, position: &[isize]) -> Option<Self::Cell> {
+        match *position {
+            [row, col] => grid.cell_at(row, col, 0),
+            _ => None,
+        }
+    }
+
+    fn is_under(_cell: Self::Cell) -> bool {
+        false
+    }
+
+    fn restore_weight(
+        _grid: &mut Grid<Self>,
+        _position: &[isize],
+        _weight: usize,
+    ) -> Result<(), MazeError> {
+        Err(MazeError::InvalidMaze(format!(
+            "{} grids have no weights",
+            Self::NAME
+        )))
+    }
+
+    fn restore_under(_grid: &mut Grid<Self>, _under: &[isize]) -> Result<(), MazeError> {
+        Err(MazeError::InvalidMaze(format!(
+            "{} grids have no under cells",
+            Self::NAME
+        )))
+    }
+}
+
+impl Persist for Regular {
+    const NAME: &'static str = "regular";
+}
+
+impl Persist for Masked {
+    const NAME: &'static str = "masked";
+}
+
+impl Persist for Polar {
+    const NAME: &'static str = "polar";
+}
+
+impl Persist for Hex {
+    const NAME: &'static str = "hex";
+}
+
+impl Persist for Triangle {
+    const NAME: &'static str = "triangle";
+}
+
+impl Persist for Upsilon {
+    const NAME: &'static str = "upsilon";
+}
+
+impl Persist for Weighted {
+    const NAME: &'static str = "weighted";
+
+    fn restore_weight(
+        grid: &mut Grid<Self>,
+        position: &[isize],
+        weight: usize,
+    ) -> Result<(), MazeError> {
+        let [row, col] = *position else {
+            return Err(invalid_position(position));
+        };
+
+        grid.try_set_weight(row, col, weight)
+    }
+}
+
+impl Persist for Weave {
+    const NAME: &'static str = "weave";
+
+    fn position(cell: WeaveCell) -> Vec<isize> {
+        match cell {
+            WeaveCell::Over(_) => vec![cell.row(), cell.col()],
+            WeaveCell::Under(_) => vec![cell.row(), cell.col(), 1],
+        }
+    }
+
+    fn find(grid: &Grid<Self>, position: &[isize]) -> Option<WeaveCell> {
+        match *position {
+            [row, col] => grid.cell_at(row, col, 0),
+            [row, col, 1] => match grid.cell_at(row, col, 0)? {
+                WeaveCell::Over(over) => {
+                    let under = WeaveCell::Under(UnderCell::new(over));
+                    grid.links(under).next().is_some().then_some(under)
+                }
+                WeaveCell::Under(_) => None,
+            },
+            _ => None,
+        }
+    }
+
+    fn is_under(cell: WeaveCell) -> bool {
+        !cell.is_over()
+    }
+
+    fn restore_under(grid: &mut Grid<Self>, under: &[isize]) -> Result<(), MazeError> {
+        let [row, col, ref ends @ ..] = *under else {
+            return Err(invalid_position(under));
+        };
+        let (from, to) = ends.split_at(ends.len() / 2);
+        let find = |position| Self::find(grid, position).ok_or_else(|| invalid_position(position));
+        let (from, to) = (find(from)?, find(to)?);
+        let Some(WeaveCell::Over(over)) = grid.cell_at(row, col, 0) else {
+            return Err(invalid_position(&[row, col]));
+        };
+
+        grid.tunnel_under(from, over, to);
+        Ok(())
+    }
+}
+
+impl Persist for ThreeD {
+    const NAME: &'static str = "3d";
+
+    fn position(cell: Self::Cell) -> Vec<isize> {
+        vec![cell.row(), cell.col(), cell.level()]
+    }
+
+    fn find(grid: &Grid<Self>, position: &[isize]) -> Option<Self::Cell> {
+        match *position {
+            [row, col, level] => grid.cell_at(row, col, level),
+            _ => None,
+        }
+    }
+}
+
+impl Persist for Cylinder {
+    const NAME: &'static str = "cylinder";
+}
+
+impl Persist for Torus {
+    const NAME: &'static str = "torus";
+}
+
+impl Persist for MobiusStrip {
+    const NAME: &'static str = "mobius";
+}
+
+impl Persist for KleinBottle {
+    const NAME: &'static str = "klein";
+}
+
+impl Persist for Cube {
+    const NAME: &'static str = "cube";
+
+    fn position(cell: Self::Cell) -> Vec<isize> {
+        vec![cell.row, cell.col, cell.face]
+    }
+
+    fn find(grid: &Grid<Self>, position: &[isize]) -> Option<Self::Cell> {
+        match *position {
+            [row, col, face] => grid.cell_at(row, col, face),
+            _ => None,
+        }
+    }
+}
+
+fn invalid_position(position: &[isize]) -> MazeError {
+    MazeError::InvalidMaze(format!("no cell at {position:?}"))
+}
+
+#[derive(Serialize, Deserialize)]
+struct Stored<K> {
+    version: u32,
+    #[serde(rename = "type")]
+    name: String,
+    kind: K,
+    links: Vec<Vec<isize>>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    weights: Vec<Vec<isize>>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    unders: Vec<Vec<isize>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    start: Option<Vec<isize>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    goal: Option<Vec<isize>>,
+}
+
+impl<K: Persist> Stored<K> {
+    fn into_grid(self) -> Result<Grid<K>, MazeError> {
+        if self.version != VERSION {
+            return Err(MazeError::InvalidMaze(format!(
+                "unsupported version {}",
+                self.version
+            )));
+        }
+        if self.name != K::NAME {
+            return Err(MazeError::InvalidMaze(format!(
+                "expected a {} grid, found {}",
+                K::NAME,
+                self.name
+            )));
+        }
+
+        let mut grid = Grid::new(self.kind, None, None);
+        for weight in &self.weights {
+            let Some((weight, position)) = weight.split_last() else {
+                return Err(invalid_position(weight));
+            };
+            let weight = usize::try_from(*weight)
+                .map_err(|_| MazeError::InvalidMaze(format!("negative weight at {position:?}")))?;
+            K::restore_weight(&mut grid, position, weight)?;
+        }
+
+        for link in &self.links {
+            let (from, to) = link.split_at(link.len() / 2);
+            let find =
+                |position| K::find(&grid, position).ok_or_else(|| invalid_position(position));
+            let (from, to) = (find(from)?, find(to)?);
+            grid.connect(from, to);
+        }
+        for under in &self.unders {
+            K::restore_under(&mut grid, under)?;
+        }
+
+        if let Some((cell, other)) = grid.invalid_links().first() {
+            return Err(MazeError::InvalidMaze(format!(
+                "passage between {:?} and {:?}, which aren't neighbours",
+                K::position(*cell),
+                K::position(*other)
+            )));
+        }
+
+        for (position, set) in [
+            (self.start, Grid::set_start as fn(&mut Grid<K>, K::Cell)),
+            (self.goal, Grid::set_goal),
+        ] {
+            if let Some(position) = position {
+                let cell = K::find(&grid, &position).ok_or_else(|| invalid_position(&position))?;
+                set(&mut grid, cell);
+            }
+        }
+
+        Ok(grid)
+    }
+}
+
+impl<K: Persist> Serialize for Grid<K> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut links = Vec::new();
+        let mut weights = Vec::new();
+        let mut unders = Vec::new();
+        for cell in self.cells() {
+            if K::is_under(cell) {
+                let mut ends = self.links(cell).map(K::position).collect::<Vec<_>>();
+                ends.sort_unstable();
+                unders.push([vec![cell.row(), cell.col()], ends.concat()].concat());
+                continue;
+            }
+            if cell.weight() != 1 {
+                let mut weight = K::position(cell);
+                weight.push(cell.weight() as isize);
+                weights.push(weight);
+            }
+
+            for other in self.links(cell) {
+                if cell < other && !K::is_under(other) {
+                    let mut link = K::position(cell);
+                    link.extend(K::position(other));
+                    links.push(link);
+                }
+            }
+        }
+        links.sort_unstable();
+        weights.sort_unstable();
+        unders.sort_unstable();
+
+        Stored {
+            version: VERSION,
+            name: K::NAME.to_string(),
+            kind: self.get_kind(),
+            links,
+            weights,
+            unders,
+            start: self.start().map(K::position),
+            goal: self.goal().map(K::position),
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de, K: Persist> Deserialize<'de> for Grid<K> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Stored::<K>::deserialize(deserializer)?
+            .into_grid()
+            .map_err(de::Error::custom)
+    }
+}
+
+impl<K: Persist> Grid<K> {
+    /// The grid in the JSON format described by [`Persist`].
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("grids to always serialize")
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, MazeError> {
+        Ok(serde_json::from_str(json)?)
+    }
+}
+
+impl Serialize for Mask {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.to_string().lines())
+    }
+}
+
+impl<'de> Deserialize<'de> for Mask {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Vec::<String>::deserialize(deserializer)?
+            .join("\n")
+            .parse()
+            .map_err(de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use super::*;
+    use crate::generators::{RecursiveBacktracker, Registry};
+
+    fn round_trip<K: Persist + Clone>(kind: &K) {
+        for generator in Registry::<K>::new().iter() {
+            for seed in 0..3 {
+                let grid = Grid::new(kind.clone(), None, None)
+                    .generate(generator, &mut StdRng::seed_from_u64(seed));
+                let json = grid.to_json();
+                let loaded = Grid::<K>::from_json(&json)
+                    .unwrap_or_else(|err| panic!("{} {}: {err}", K::NAME, generator.name()));
+
+                assert_eq!(loaded.to_json(), json, "{} {}", K::NAME, generator.name());
+                for cell in grid.cells() {
+                    assert!(grid.links(cell).all(|other| loaded.are_linked(cell, other)));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn every_generator_round_trips_for_every_kind() {
+        let mut mask = Mask::new(6, 6);
+        mask[2][2] = false;
+
+        round_trip(&Regular::new(6, 6));
+        round_trip(&Masked::new(mask));
+        round_trip(&Polar::new(5));
+        round_trip(&Hex::new(6, 6));
+        round_trip(&Triangle::new(6, 8));
+        round_trip(&Upsilon::new(6, 6));
+        round_trip(&Weighted::new(6, 6));
+        round_trip(&Weave::new(8, 8));
+        round_trip(&ThreeD::new(4, 4, 3));
+        round_trip(&Cylinder::new(6, 6));
+        round_trip(&Torus::new(6, 6));
+        round_trip(&MobiusStrip::new(6, 6));
+        round_trip(&KleinBottle::new(6, 6));
+        round_trip(&Cube::new(3));
+    }
+
+    #[test]
+    fn weave_unders_load_in_any_order() {
+        let grid = Grid::new(Weave::new(8, 8), None, None)
+            .generate(&RecursiveBacktracker, &mut StdRng::seed_from_u64(1));
+        let loaded = Grid::<Weave>::from_json(&grid.to_json()).expect("weave grid to load");
+
+        assert_eq!(loaded.to_json(), grid.to_json());
+    }
+}
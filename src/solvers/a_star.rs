@@ -0,0 +1,56 @@
+use std::{cmp::Reverse, collections::BinaryHeap};
+
+use rustc_hash::FxHashMap;
+
+use crate::{
+    cell::CellKind,
+    grid::Grid,
+    kind::Kind,
+    solvers::{backtrack, Solution, Solver},
+};
+
+/// A* search guided by [`Kind::heuristic`], finding the cheapest route by cell weight.
+pub struct AStar;
+
+impl<K: Kind> Solver<K> for AStar {
+    fn name(&self) -> &'static str {
+        "a_star"
+    }
+
+    fn solve(&self, grid: &Grid<K>, start: K::Cell, goal: K::Cell) -> Option<Solution<K::Cell>> {
+        let kind = grid.get_kind();
+
+        let mut costs = FxHashMap::from_iter([(start, 0)]);
+        let mut parents = FxHashMap::default();
+        let mut pending = BinaryHeap::from_iter([Reverse((kind.heuristic(start, goal), 0, start))]);
+        let mut expanded = 0;
+
+        while let Some(Reverse((_, cost, cell))) = pending.pop() {
+            if cost > costs[&cell] {
+                // a cheaper route to this cell was already expanded
+                continue;
+            }
+            if cell == goal {
+                return Some(Solution {
+                    path: backtrack(&parents, goal),
+                    expanded,
+                });
+            }
+            expanded += 1;
+
+            for neighbour in grid.links(cell) {
+                let cost = cost + neighbour.weight();
+                if costs.get(&neighbour).is_some_and(|known| *known <= cost) {
+                    continue;
+                }
+
+                costs.insert(neighbour, cost);
+                parents.insert(neighbour, cell);
+                let estimate = cost + kind.heuristic(neighbour, goal);
+                pending.push(Reverse((estimate, cost, neighbour)));
+            }
+        }
+
+        None
+    }
+}
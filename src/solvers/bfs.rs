@@ -0,0 +1,43 @@
+use std::collections::VecDeque;
+
+use rustc_hash::FxHashMap;
+
+use crate::{
+    grid::Grid,
+    kind::Kind,
+    solvers::{backtrack, Solution, Solver},
+};
+
+/// Breadth-first search, finding the route with the fewest steps regardless of cell weights.
+pub struct Bfs;
+
+impl<K: Kind> Solver<K> for Bfs {
+    fn name(&self) -> &'static str {
+        "bfs"
+    }
+
+    fn solve(&self, grid: &Grid<K>, start: K::Cell, goal: K::Cell) -> Option<Solution<K::Cell>> {
+        let mut parents = FxHashMap::default();
+        let mut pending = VecDeque::from([start]);
+        let mut expanded = 0;
+
+        while let Some(cell) = pending.pop_front() {
+            if cell == goal {
+                return Some(Solution {
+                    path: backtrack(&parents, goal),
+                    expanded,
+                });
+            }
+            expanded += 1;
+
+            for neighbour in grid.links(cell) {
+                if neighbour != start && !parents.contains_key(&neighbour) {
+                    parents.insert(neighbour, cell);
+                    pending.push_back(neighbour);
+                }
+            }
+        }
+
+        None
+    }
+}
@@ -0,0 +1,104 @@
+use std::collections::VecDeque;
+
+use rustc_hash::FxHashMap;
+
+use crate::{
+    cell::CellKind,
+    grid::Grid,
+    kind::Kind,
+    solvers::{backtrack, Solution, Solver},
+};
+
+/// Breadth-first search from both ends at once, growing whichever frontier is smaller a whole
+/// layer at a time until the two meet. Like [`Bfs`](super::Bfs) it finds the route with the
+/// fewest steps, ignoring cell weights.
+pub struct Bidirectional;
+
+struct Side<C> {
+    parents: FxHashMap<C, C>,
+    depths: FxHashMap<C, usize>,
+    frontier: VecDeque<C>,
+}
+
+impl<C: CellKind> Side<C> {
+    fn new(root: C) -> Self {
+        Self {
+            parents: FxHashMap::default(),
+            depths: FxHashMap::from_iter([(root, 0)]),
+            frontier: VecDeque::from([root]),
+        }
+    }
+
+    /// Expands the current layer, returning the cell reached by both sides with the shortest
+    /// route through it, if the sides have met.
+    fn expand<K: Kind<Cell = C>>(
+        &mut self,
+        grid: &Grid<K>,
+        other: &Self,
+        expanded: &mut usize,
+    ) -> Option<C> {
+        let mut meeting = None;
+
+        for _ in 0..self.frontier.len() {
+            let cell = self.frontier.pop_front()?;
+            let depth = self.depths[&cell] + 1;
+            *expanded += 1;
+
+            for neighbour in grid.links(cell) {
+                if self.depths.contains_key(&neighbour) {
+                    continue;
+                }
+
+                self.depths.insert(neighbour, depth);
+                self.parents.insert(neighbour, cell);
+                self.frontier.push_back(neighbour);
+
+                if let Some(rest) = other.depths.get(&neighbour) {
+                    if meeting.is_none_or(|(_, length)| depth + rest < length) {
+                        meeting = Some((neighbour, depth + rest));
+                    }
+                }
+            }
+        }
+
+        meeting.map(|(cell, _)| cell)
+    }
+}
+
+impl<K: Kind> Solver<K> for Bidirectional {
+    fn name(&self) -> &'static str {
+        "bidirectional"
+    }
+
+    fn solve(&self, grid: &Grid<K>, start: K::Cell, goal: K::Cell) -> Option<Solution<K::Cell>> {
+        if start == goal {
+            return Some(Solution {
+                path: vec![start],
+                expanded: 0,
+            });
+        }
+
+        let mut forward = Side::new(start);
+        let mut backward = Side::new(goal);
+        let mut expanded = 0;
+
+        while !forward.frontier.is_empty() && !backward.frontier.is_empty() {
+            let meeting = if forward.frontier.len() <= backward.frontier.len() {
+                forward.expand(grid, &backward, &mut expanded)
+            } else {
+                backward.expand(grid, &forward, &mut expanded)
+            };
+
+            if let Some(meeting) = meeting {
+                let mut path = backtrack(&forward.parents, meeting);
+                let mut rest = backtrack(&backward.parents, meeting);
+                rest.pop();
+                path.extend(rest.into_iter().rev());
+
+                return Some(Solution { path, expanded });
+            }
+        }
+
+        None
+    }
+}
@@ -0,0 +1,72 @@
+use std::collections::VecDeque;
+
+use rustc_hash::{FxHashMap, FxHashSet};
+
+use crate::{
+    grid::Grid,
+    kind::Kind,
+    solvers::{backtrack, Solution, Solver},
+};
+
+/// Fills in every dead end other than the start and goal, then the passages that become dead
+/// ends in turn, until only the routes between the start and goal are left open.
+///
+/// In a perfect maze what is left is the solution. Otherwise this walks the fewest steps through
+/// what is left. The number expanded is the number of cells filled.
+pub struct DeadEndFilling;
+
+impl<K: Kind> Solver<K> for DeadEndFilling {
+    fn name(&self) -> &'static str {
+        "dead_end_filling"
+    }
+
+    fn solve(&self, grid: &Grid<K>, start: K::Cell, goal: K::Cell) -> Option<Solution<K::Cell>> {
+        let mut open_passages = grid
+            .cells()
+            .into_iter()
+            .map(|cell| (cell, grid.links(cell).count()))
+            .collect::<FxHashMap<_, _>>();
+        let mut filled = FxHashSet::default();
+        let mut pending = grid
+            .dead_ends()
+            .into_iter()
+            .filter(|cell| *cell != start && *cell != goal)
+            .collect::<Vec<_>>();
+
+        while let Some(cell) = pending.pop() {
+            if !filled.insert(cell) {
+                continue;
+            }
+
+            for other in grid.links(cell).filter(|other| !filled.contains(other)) {
+                let open = open_passages
+                    .get_mut(&other)
+                    .expect("linked cell to be in the grid");
+                *open -= 1;
+                if *open == 1 && other != start && other != goal {
+                    pending.push(other);
+                }
+            }
+        }
+
+        let mut parents = FxHashMap::default();
+        let mut queue = VecDeque::from([start]);
+        while let Some(cell) = queue.pop_front() {
+            if cell == goal {
+                return Some(Solution {
+                    path: backtrack(&parents, goal),
+                    expanded: filled.len(),
+                });
+            }
+
+            for other in grid.links(cell) {
+                if other != start && !filled.contains(&other) && !parents.contains_key(&other) {
+                    parents.insert(other, cell);
+                    queue.push_back(other);
+                }
+            }
+        }
+
+        None
+    }
+}
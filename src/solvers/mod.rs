@@ -0,0 +1,62 @@
+mod a_star;
+mod bfs;
+mod bidirectional;
+mod dead_end_filling;
+mod tremaux;
+mod wall_follower;
+
+use rustc_hash::FxHashMap;
+
+use crate::{cell::CellKind, grid::Grid, kind::Kind};
+
+pub use a_star::AStar;
+pub use bfs::Bfs;
+pub use bidirectional::Bidirectional;
+pub use dead_end_filling::DeadEndFilling;
+pub use tremaux::Tremaux;
+pub use wall_follower::{Hand, WallFollower};
+
+/// A route found from one cell to another, along with how hard the solver worked to find it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Solution<C> {
+    /// Cells from the start to the goal, both included.
+    pub path: Vec<C>,
+    /// How many cells the solver took off its frontier and looked past.
+    pub expanded: usize,
+}
+
+/// A maze solving algorithm that finds a route between two cells of a [`Grid`] of kind `K`.
+pub trait Solver<K: Kind> {
+    fn name(&self) -> &'static str;
+    fn solve(&self, grid: &Grid<K>, start: K::Cell, goal: K::Cell) -> Option<Solution<K::Cell>>;
+}
+
+impl<K: Kind> Grid<K> {
+    /// Solves from the grid's start to its goal, if both are set and connected.
+    pub fn solve(&self, solver: &(impl Solver<K> + ?Sized)) -> Option<Solution<K::Cell>> {
+        solver.solve(self, self.start()?, self.goal()?)
+    }
+}
+
+/// Solvers that work on any kind.
+pub fn all<K: Kind>() -> Vec<Box<dyn Solver<K>>> {
+    vec![
+        Box::new(AStar),
+        Box::new(Bfs),
+        Box::new(Bidirectional),
+        Box::new(Tremaux),
+        Box::new(DeadEndFilling),
+    ]
+}
+
+/// Walks `parents` back from `cell` to the cell without a parent, returning the cells in order
+/// from that root to `cell`.
+fn backtrack<C: CellKind>(parents: &FxHashMap<C, C>, cell: C) -> Vec<C> {
+    let mut path = vec![cell];
+    while let Some(parent) = parents.get(path.last().expect("path to be non-empty")) {
+        path.push(*parent);
+    }
+    path.reverse();
+
+    path
+}
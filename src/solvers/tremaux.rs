@@ -0,0 +1,69 @@
+use rustc_hash::{FxHashMap, FxHashSet};
+
+use crate::{
+    grid::Grid,
+    kind::Kind,
+    solvers::{Solution, Solver},
+};
+
+/// Trémaux's algorithm: mark each passage as it is walked, never walk one a third time, and turn
+/// back on reaching an already visited cell by a fresh passage.
+///
+/// The route is everything walked, dead ends and backtracking included. Passages still marked
+/// once when the goal is reached make up a direct route from the start.
+pub struct Tremaux;
+
+impl<K: Kind> Solver<K> for Tremaux {
+    fn name(&self) -> &'static str {
+        "tremaux"
+    }
+
+    fn solve(&self, grid: &Grid<K>, start: K::Cell, goal: K::Cell) -> Option<Solution<K::Cell>> {
+        let mut marks = FxHashMap::default();
+        let mut visited = FxHashSet::from_iter([start]);
+        let mut route = vec![start];
+        let mut previous = None;
+        let mut cell = start;
+
+        let passage = |cell, other| {
+            if cell < other {
+                (cell, other)
+            } else {
+                (other, cell)
+            }
+        };
+
+        while cell != goal {
+            let came_by_fresh_passage =
+                previous.is_some_and(|previous| marks[&passage(cell, previous)] == 1);
+            let next = match previous {
+                // an old cell reached by a new passage: treat it like a dead end
+                Some(previous) if came_by_fresh_passage && !visited.insert(cell) => previous,
+                _ => {
+                    visited.insert(cell);
+                    grid.links(cell)
+                        .map(|other| {
+                            (
+                                marks.get(&passage(cell, other)).copied().unwrap_or(0),
+                                other,
+                            )
+                        })
+                        .filter(|(marked, other)| *marked < 2 && Some(*other) != previous)
+                        .min_by_key(|(marked, _)| *marked)
+                        .map(|(_, other)| other)
+                        .or(previous.filter(|previous| marks[&passage(cell, *previous)] < 2))?
+                }
+            };
+
+            *marks.entry(passage(cell, next)).or_insert(0) += 1;
+            route.push(next);
+            previous = Some(cell);
+            cell = next;
+        }
+
+        Some(Solution {
+            expanded: visited.len(),
+            path: route,
+        })
+    }
+}
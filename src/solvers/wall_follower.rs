@@ -0,0 +1,62 @@
+use rustc_hash::FxHashSet;
+
+use crate::{
+    grid::Grid,
+    kind::Heading,
+    solvers::{Solution, Solver},
+};
+
+/// Which hand the walker keeps on the wall.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Hand {
+    Left,
+    Right,
+}
+
+/// Walks with one hand on the wall, taking the first open turn on that side at every cell.
+///
+/// The route is everything walked, dead ends included. It only reaches the goal if the goal is
+/// on the same wall as the start, so this gives up once it is back where it began facing the
+/// same way.
+pub struct WallFollower(pub Hand);
+
+impl<K: Heading> Solver<K> for WallFollower {
+    fn name(&self) -> &'static str {
+        "wall_follower"
+    }
+
+    fn solve(&self, grid: &Grid<K>, start: K::Cell, goal: K::Cell) -> Option<Solution<K::Cell>> {
+        let mut route = vec![start];
+        let mut seen = FxHashSet::default();
+        let mut previous = None;
+        let mut cell = start;
+
+        while cell != goal {
+            let open = K::around(grid, cell)
+                .into_iter()
+                .filter(|other| grid.are_linked(cell, *other))
+                .collect::<Vec<_>>();
+
+            // turning towards the hand from straight back gives the first opening on that side
+            let back = previous.and_then(|previous| open.iter().position(|c| *c == previous));
+            let next = match (back, self.0) {
+                (Some(back), Hand::Right) => open[(back + open.len() - 1) % open.len()],
+                (Some(back), Hand::Left) => open[(back + 1) % open.len()],
+                (None, _) => *open.first()?,
+            };
+
+            if !seen.insert((cell, next)) {
+                return None;
+            }
+
+            route.push(next);
+            previous = Some(cell);
+            cell = next;
+        }
+
+        Some(Solution {
+            expanded: route.len() - 1,
+            path: route,
+        })
+    }
+}
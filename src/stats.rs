@@ -0,0 +1,227 @@
+use core::fmt;
+use std::collections::BTreeMap;
+
+use rustc_hash::FxHashSet;
+
+use crate::{grid::Grid, kind::Kind};
+
+/// Measures of how a maze is shaped and how hard it is to solve.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MazeStats {
+    pub cells: usize,
+    /// Cells with a single passage.
+    pub dead_ends: usize,
+    /// How many cells have each number of passages, for cells with three or more.
+    pub junctions: BTreeMap<usize, usize>,
+    /// Cells with two passages that carry straight on, as [`Kind::is_straight`] decides.
+    pub straights: usize,
+    /// Cells with two passages that turn a corner.
+    pub turns: usize,
+    /// `straights / turns`, or `None` if there are no turns to divide by.
+    pub straight_turn_ratio: Option<f32>,
+    /// How much of the maze is long passage, after Walter Pullen's "river" texture: the cells in
+    /// corridor runs less one for each run, as a share of all cells. Long windy passages score
+    /// near one and lots of short branches near zero.
+    pub river: f32,
+    /// Lengths in cells of the runs of two-passage cells between dead ends and junctions.
+    pub mean_corridor: f32,
+    pub max_corridor: usize,
+    /// Cells on the route from the start to the goal, if both are set and connected.
+    pub solution_length: Option<usize>,
+    /// Cells on the solution as a share of all cells.
+    pub solution_coverage: Option<f32>,
+    /// Cells on the solution that offer more than one way onwards.
+    pub decision_points: Option<usize>,
+    /// Independent loops in the maze, as `passages - cells + 1`, which is zero for a perfect maze.
+    pub loops: usize,
+}
+
+impl<K: Kind> Grid<K> {
+    pub fn stats(&self) -> MazeStats {
+        let cells = self.cells();
+        let degree = |cell| self.links(cell).count();
+
+        let dead_ends = self.dead_ends().len();
+
+        let mut junctions = BTreeMap::new();
+        for cell in cells.iter().filter(|cell| degree(**cell) >= 3) {
+            *junctions.entry(degree(*cell)).or_insert(0) += 1;
+        }
+
+        let corridors = cells
+            .iter()
+            .copied()
+            .filter(|cell| degree(*cell) == 2)
+            .collect::<Vec<_>>();
+        let straights = corridors
+            .iter()
+            .filter(|cell| {
+                let [from, to] = [0, 1].map(|i| {
+                    self.links(**cell)
+                        .nth(i)
+                        .expect("corridors to have two links")
+                });
+                K::is_straight(self, **cell, from, to)
+            })
+            .count();
+        let turns = corridors.len() - straights;
+
+        let lengths = self.corridor_lengths(&corridors);
+        let mean_corridor = if lengths.is_empty() {
+            0.0
+        } else {
+            lengths.iter().sum::<usize>() as f32 / lengths.len() as f32
+        };
+
+        let solution = self.path();
+        let decision_points = solution.as_ref().map(|path| {
+            let cells = path.cells();
+            cells[..cells.len() - 1]
+                .iter()
+                .enumerate()
+                .filter(|(i, cell)| {
+                    let onwards = degree(**cell) - usize::from(*i > 0);
+                    onwards > 1
+                })
+                .count()
+        });
+
+        let passages = cells.iter().map(|cell| degree(*cell)).sum::<usize>() / 2;
+
+        MazeStats {
+            cells: cells.len(),
+            dead_ends,
+            junctions,
+            straights,
+            turns,
+            straight_turn_ratio: (turns > 0).then(|| straights as f32 / turns as f32),
+            river: lengths.iter().map(|length| length - 1).sum::<usize>() as f32
+                / cells.len().max(1) as f32,
+            mean_corridor,
+            max_corridor: lengths.iter().copied().max().unwrap_or(0),
+            solution_length: solution.as_ref().map(|path| path.len()),
+            solution_coverage: solution
+                .as_ref()
+                .map(|path| path.len() as f32 / cells.len() as f32),
+            decision_points,
+            loops: (passages + 1).saturating_sub(cells.len()),
+        }
+    }
+
+    /// Splits `corridors`, the cells with two passages, into connected runs and measures them.
+    fn corridor_lengths(&self, corridors: &[K::Cell]) -> Vec<usize> {
+        let corridors = corridors.iter().copied().collect::<FxHashSet<_>>();
+        let mut seen = FxHashSet::default();
+        let mut lengths = Vec::new();
+
+        for cell in &corridors {
+            if !seen.insert(*cell) {
+                continue;
+            }
+
+            let mut length = 1;
+            let mut pending = vec![*cell];
+            while let Some(cell) = pending.pop() {
+                for other in self.links(cell) {
+                    if corridors.contains(&other) && seen.insert(other) {
+                        length += 1;
+                        pending.push(other);
+                    }
+                }
+            }
+            lengths.push(length);
+        }
+
+        lengths
+    }
+}
+
+impl fmt::Display for MazeStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "cells:          {}", self.cells)?;
+        writeln!(
+            f,
+            "dead ends:      {} ({:.1}%)",
+            self.dead_ends,
+            100.0 * self.dead_ends as f32 / self.cells.max(1) as f32
+        )?;
+        for (degree, count) in &self.junctions {
+            writeln!(f, "{degree}-way junctions: {count}")?;
+        }
+        write!(f, "straight/turn:  {}/{}", self.straights, self.turns)?;
+        match self.straight_turn_ratio {
+            Some(ratio) => writeln!(f, " ({ratio:.2})")?,
+            None => writeln!(f)?,
+        }
+        writeln!(f, "river:          {:.2}", self.river)?;
+        writeln!(
+            f,
+            "corridors:      {:.1} mean, {} max",
+            self.mean_corridor, self.max_corridor
+        )?;
+        if let (Some(length), Some(coverage), Some(decisions)) = (
+            self.solution_length,
+            self.solution_coverage,
+            self.decision_points,
+        ) {
+            writeln!(
+                f,
+                "solution:       {length} cells ({:.1}%), {decisions} decisions",
+                100.0 * coverage
+            )?;
+        }
+        write!(f, "loops:          {}", self.loops)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        cell::{RegularCell, TriangleCell},
+        grid::Grid,
+        kind::{Regular, Triangle},
+    };
+
+    #[test]
+    fn stats_of_a_single_passage() {
+        let cells = (0..6)
+            .map(|col| RegularCell::new(0, col))
+            .collect::<Vec<_>>();
+        let mut grid = Grid::new(Regular::new(1, 6), Some(cells[0]), Some(cells[5]));
+        for pair in cells.windows(2) {
+            grid.link(pair[0], pair[1]);
+        }
+
+        let stats = grid.stats();
+        assert_eq!(stats.straight_turn_ratio, None);
+        assert!((stats.river - 3.0 / 6.0).abs() < f32::EPSILON);
+        assert_eq!(stats.decision_points, Some(0));
+    }
+
+    #[test]
+    fn stats_without_corridors_or_a_route() {
+        let (start, goal) = (RegularCell::new(0, 0), RegularCell::new(1, 1));
+        let mut grid = Grid::new(Regular::new(2, 2), Some(start), Some(goal));
+        grid.link(start, RegularCell::new(0, 1));
+
+        let stats = grid.stats();
+        assert_eq!(stats.straight_turn_ratio, None);
+        assert_eq!(stats.river, 0.0);
+        assert_eq!(stats.solution_length, None);
+    }
+
+    #[test]
+    fn every_way_through_a_triangle_turns() {
+        let cells = (0..5)
+            .map(|col| TriangleCell::new(0, col))
+            .collect::<Vec<_>>();
+        let mut grid = Grid::new(Triangle::new(1, 5), None, None);
+        for pair in cells.windows(2) {
+            grid.link(pair[0], pair[1]);
+        }
+
+        let stats = grid.stats();
+        assert_eq!((stats.straights, stats.turns), (0, 3));
+        assert_eq!(stats.straight_turn_ratio, Some(0.0));
+    }
+}
@@ -0,0 +1,106 @@
+use image::Rgb;
+
+/// How grids are drawn by [`Render`](crate::Render) and the `save_*`/`write_*` methods.
+///
+/// The default is black one pixel walls on white, with distances shaded from white to green
+/// and weighted cells in red.
+#[derive(Debug, Clone)]
+pub struct RenderOptions {
+    pub wall: Rgb<u8>,
+    /// Wall thickness in pixels.
+    pub wall_thickness: u32,
+    pub background: Rgb<u8>,
+    /// Empty space in pixels around the maze.
+    pub margin: u32,
+    /// Colours cells by their distance, from the start of the gradient at distance zero to its
+    /// end at the furthest cell.
+    pub gradient: Gradient,
+    /// Colour of cells with a weight above one when shading by distance.
+    pub weighted: Rgb<u8>,
+    /// Whether to shade cells at all, rather than only drawing walls.
+    pub shade: bool,
+    /// Colour of a line drawn through the centres of the cells from the start to the goal, when
+    /// both are set.
+    pub solution: Option<Rgb<u8>>,
+    pub solution_thickness: u32,
+    /// Whether to mark the start and goal cells with a dot.
+    pub markers: bool,
+    pub start_marker: Rgb<u8>,
+    pub goal_marker: Rgb<u8>,
+}
+
+impl Default for RenderOptions {
+    fn default() -> Self {
+        Self {
+            wall: Rgb([0, 0, 0]),
+            wall_thickness: 1,
+            background: Rgb([255, 255, 255]),
+            margin: 0,
+            gradient: Gradient::new(Rgb([255, 255, 255]), Rgb([0, 128, 0])),
+            weighted: Rgb([255, 0, 0]),
+            shade: true,
+            solution: None,
+            solution_thickness: 2,
+            markers: false,
+            start_marker: Rgb([0, 128, 255]),
+            goal_marker: Rgb([255, 0, 0]),
+        }
+    }
+}
+
+/// A colour map over `0.0..=1.0` made of colour stops with linear interpolation between them.
+#[derive(Debug, Clone)]
+pub struct Gradient {
+    stops: Vec<(f32, Rgb<u8>)>,
+}
+
+impl Gradient {
+    pub fn new(from: Rgb<u8>, to: Rgb<u8>) -> Self {
+        Self {
+            stops: vec![(0.0, from), (1.0, to)],
+        }
+    }
+
+    /// Spreads `colors` evenly over the gradient, or `None` without any colours.
+    pub fn evenly(colors: impl IntoIterator<Item = Rgb<u8>>) -> Option<Self> {
+        let colors = colors.into_iter().collect::<Vec<_>>();
+        let last = colors.len().saturating_sub(1).max(1) as f32;
+
+        Self::from_stops(
+            colors
+                .into_iter()
+                .enumerate()
+                .map(|(i, color)| (i as f32 / last, color)),
+        )
+    }
+
+    /// Builds a gradient from `(position, colour)` stops, in any order, or `None` without any
+    /// stops. Positions outside the first and last stop take the colour of the nearest one.
+    pub fn from_stops(stops: impl IntoIterator<Item = (f32, Rgb<u8>)>) -> Option<Self> {
+        let mut stops = stops.into_iter().collect::<Vec<_>>();
+        if stops.is_empty() {
+            return None;
+        }
+        stops.sort_by(|(a, _), (b, _)| a.total_cmp(b));
+
+        Some(Self { stops })
+    }
+
+    pub fn color_at(&self, position: f32) -> Rgb<u8> {
+        let after = self.stops.partition_point(|(at, _)| *at <= position);
+        if after == 0 {
+            return self.stops[0].1;
+        }
+        if after == self.stops.len() {
+            return self.stops[after - 1].1;
+        }
+
+        let (from_at, Rgb(from)) = self.stops[after - 1];
+        let (to_at, Rgb(to)) = self.stops[after];
+        let t = (position - from_at) / (to_at - from_at);
+
+        Rgb(std::array::from_fn(|i| {
+            (f32::from(from[i]) + t * (f32::from(to[i]) - f32::from(from[i]))).round() as u8
+        }))
+    }
+}